@@ -1,25 +1,78 @@
-var cacheName = 'egui-template-pwa';
+// `{{BUILD_HASH}}` is replaced with a hash of the wasm binary by `cargo xtask stamp-sw`, run
+// by the post_build hook in Trunk.toml, so every build gets its own cache and old ones can be
+// cleaned up.
+var buildHash = '{{BUILD_HASH}}';
+// Without the hook, every build would share one cache and never update. Refuse to install.
+if (buildHash.charAt(0) === '{') {
+  throw new Error('sw.js was not stamped with a build hash, see Trunk.toml');
+}
+// Generated by `cargo xtask generate`, do not edit by hand:
+var cachePrefix = 'eframe-template-';
 var filesToCache = [
   './',
   './index.html',
   './eframe_template.js',
   './eframe_template_bg.wasm',
+  './manifest.json',
+  './icon-1024.png',
+  './icon-256.png',
+  './icon_ios_touch_192.png',
+  './maskable_icon_x512.png',
 ];
+// End of generated code.
+var cacheName = cachePrefix + buildHash;
 
-/* Start the service worker and cache all of the app's content */
+/* Cache all of the app's content. The new worker then waits until the app tells it to take over. */
 self.addEventListener('install', function (e) {
   e.waitUntil(
-    caches.open(cacheName).then(function (cache) {
-      return cache.addAll(filesToCache);
-    })
+    caches.open(cacheName)
+      .then(function (cache) {
+        return cache.addAll(filesToCache);
+      })
+      .then(function () {
+        return self.clients.matchAll({ type: 'window', includeUncontrolled: true });
+      })
+      .then(function (clients) {
+        clients.forEach(function (client) {
+          client.postMessage({ type: 'UPDATE_AVAILABLE', buildHash: buildHash });
+        });
+      })
   );
 });
 
+/* Remove the caches of previous builds once this one is in charge */
+self.addEventListener('activate', function (e) {
+  e.waitUntil(
+    caches.keys()
+      .then(function (keys) {
+        return Promise.all(keys.map(function (key) {
+          if (key.startsWith(cachePrefix) && key !== cacheName) {
+            return caches.delete(key);
+          }
+        }));
+      })
+      .then(function () {
+        return self.clients.claim();
+      })
+  );
+});
+
+/* Sent by the app when the user accepts the update */
+self.addEventListener('message', function (e) {
+  if (e.data && e.data.type === 'SKIP_WAITING') {
+    self.skipWaiting();
+  }
+});
+
 /* Serve cached content when offline */
 self.addEventListener('fetch', function (e) {
   e.respondWith(
-    caches.match(e.request).then(function (response) {
-      return response || fetch(e.request);
-    })
+    caches.open(cacheName)
+      .then(function (cache) {
+        return cache.match(e.request);
+      })
+      .then(function (response) {
+        return response || fetch(e.request);
+      })
   );
 });
@@ -0,0 +1,100 @@
+//! - With the `cjk` feature, checks that `fonts/NotoSansSC-Regular.otf`, which is bundled
+//!   then, has been downloaded (it's too big to keep in git). See `fonts/README.md`.
+//! - Writes the section of `CHANGELOG.md` for this version to `$OUT_DIR/whats_new.md`,
+//!   for the "What's New" page of the help.
+//! - Sets `GIT_COMMIT` and `BUILD_DATE` for the About page. `SOURCE_DATE_EPOCH` overrides
+//!   the date, for reproducible builds.
+
+use std::process::Command;
+
+const CJK_FONT: &str = "fonts/NotoSansSC-Regular.otf";
+const CHANGELOG: &str = "CHANGELOG.md";
+
+fn main() {
+    println!("cargo:rerun-if-changed={CJK_FONT}");
+    cjk_font();
+
+    println!("cargo:rerun-if-changed={CHANGELOG}");
+    whats_new();
+
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    if let Ok(head) = std::fs::read_to_string(".git/HEAD") {
+        if let Some(branch) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{branch}");
+        }
+    }
+    println!("cargo:rustc-env=GIT_COMMIT={}", git_commit());
+    println!("cargo:rustc-env=BUILD_DATE={}", build_date());
+}
+
+fn cjk_font() {
+    if std::env::var_os("CARGO_FEATURE_CJK").is_none() {
+        return;
+    }
+    if !std::path::Path::new(CJK_FONT).exists() {
+        eprintln!("error: the `cjk` feature needs {CJK_FONT}, see fonts/README.md");
+        std::process::exit(1);
+    }
+}
+
+/// The `## <version>` section of the changelog, up to the next `## ` heading.
+fn whats_new() {
+    let version = std::env::var("CARGO_PKG_VERSION").unwrap();
+    let changelog = std::fs::read_to_string(CHANGELOG).unwrap_or_default();
+    let mut section = String::new();
+    let mut inside = false;
+    for line in changelog.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            let heading_version = heading.split_whitespace().next().unwrap_or_default();
+            inside = heading_version == version;
+        }
+        if inside {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+    if section.is_empty() {
+        println!("cargo:warning={CHANGELOG} has no section for version {version}");
+        section = format!("## {version}\n");
+    }
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    std::fs::write(format!("{out_dir}/whats_new.md"), section).unwrap();
+}
+
+/// The short hash of `HEAD`, or "unknown" outside of a git checkout.
+fn git_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+/// Today, or the day of `SOURCE_DATE_EPOCH`, as YYYY-MM-DD in UTC.
+fn build_date() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        });
+    // Howard Hinnant's days-to-civil algorithm.
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
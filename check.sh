@@ -2,10 +2,14 @@
 # This scripts runs various CI-like checks in a convenient way.
 set -eux
 
+# `--all-features` includes `cjk`, which needs this font, see fonts/README.md.
+[ -f fonts/NotoSansSC-Regular.otf ] || curl -sSfL -o fonts/NotoSansSC-Regular.otf \
+    https://github.com/notofonts/noto-cjk/raw/main/Sans/SubsetOTF/SC/NotoSansSC-Regular.otf
 cargo check --workspace --all-targets
 cargo check --workspace --all-features --lib --target wasm32-unknown-unknown
 cargo fmt --all -- --check
 cargo clippy --workspace --all-targets --all-features --  -D warnings -W clippy::all
 cargo test --workspace --all-targets --all-features
 cargo test --workspace --doc
+cargo xtask check
 trunk build
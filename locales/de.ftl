@@ -0,0 +1,238 @@
+# German
+
+## Menus
+
+menu-file = Datei
+menu-menu = Menü
+menu-view = Ansicht
+menu-help = Hilfe
+menu-language = Sprache
+save-screenshot-ellipsis = Bildschirmfoto speichern…
+quit = Beenden
+right-side-panel = Rechte Seitenleiste
+left-side-panel = Linke Seitenleiste
+help-topics = Hilfethemen
+keyboard-shortcuts = Tastenkürzel
+whats-new = Neuigkeiten
+about = Über
+take-tour = Rundgang starten
+direction-auto = Automatisch
+direction-ltr = Links nach rechts
+direction-rtl = Rechts nach links
+
+## Windows
+
+style-settings = Stileinstellungen
+contract-explorer = Vertrags-Explorer
+fonts = Schriftarten
+accessibility = Barrierefreiheit
+contrast-report = Kontrastbericht
+contrast-report-ellipsis = Kontrastbericht…
+icons = Symbole
+links = Links
+log-console = Protokoll
+save-screenshot = Bildschirmfoto speichern
+window = Fenster
+alert = Hinweis
+
+## Panels
+
+side-panel = Seitenleiste
+setting1 = Einstellung1:{" "}
+setting2 = Einstellung2:{" "}
+setting3 = Einstellung3:{" "}
+slider = Schieberegler:{" "}
+panel-button = Schaltfläche
+collapsing-header = Ausklappbarer Bereich
+collapsing-label = Beschriftung
+source-code = Quellcode.
+
+## Notifications
+
+update-available = Update verfügbar — neu laden
+storage-full = Der Speicher ist voll, einige Daten wurden nicht gespeichert
+storage-error = Speicherfehler: { $error }
+file-save-failed = { $path } konnte nicht gespeichert werden: { $error }
+file-download-failed = Download fehlgeschlagen: { $error }
+screenshot-saved = Bildschirmfoto gespeichert unter { $location }
+link-blocked = Link zu { $url } blockiert
+log-exported = Protokoll gespeichert unter { $location }
+notification-dismiss = Schließen
+
+## Screenshots
+
+screenshot-region = Bereich
+screenshot-whole-app = Ganze App
+screenshot-scale = Skalierung
+screenshot-save = Speichern
+screenshot-region-hidden = { $region } wird nicht mehr angezeigt
+screenshot-region-off-screen = { $region } ist nicht auf dem Bildschirm
+screenshot-encode-failed = Bildschirmfoto konnte nicht kodiert werden: { $error }
+region-menu-bar = Menüleiste
+region-bottom-bar = Untere Leiste
+region-left-panel = Linke Seitenleiste
+region-right-panel = Rechte Seitenleiste
+region-connect-button = Schaltfläche „Verbinden“
+
+## Contract explorer
+
+contract-count = { $count ->
+    [0] Mit diesem Code wurden keine Verträge instanziiert.
+    [one] Mit diesem Code wurde ein Vertrag instanziiert.
+   *[other] Mit diesem Code wurden { $count } Verträge instanziiert.
+}
+contract-network = Netzwerk:{" "}
+contract-refresh = Aktualisieren
+contract-codes = Codes
+contract-contracts = Verträge
+contract-details = Details
+contract-schema = Schema
+contract-select-code = Wähle eine Code-ID, um ihre Verträge aufzulisten.
+contract-nothing-selected = Nichts ausgewählt.
+contract-code-id = Code-ID
+contract-code-hash = Code-Hash
+contract-code-creator = Code-Ersteller
+contract-address = Adresse
+contract-label = Bezeichnung
+contract-admin = Admin
+contract-creator = Ersteller
+contract-schema-hint = Füge das JSON-Schema des Vertrags ein (ein einzelnes Nachrichtenschema oder ein vollständiges API-Schema):
+contract-invalid-json = Ungültiges JSON: { $error }
+contract-message = Nachricht
+contract-no-variants = In diesem Schema wurden keine Nachrichtenvarianten gefunden.
+contract-copy-message = Nachricht kopieren
+contract-send-hint = Abfragen sind auf Secret verschlüsselt; sende dies mit einer Wallet oder secretcli.
+contract-field-required = `{ $field }` ist erforderlich
+contract-field-integer = `{ $field }` muss eine ganze Zahl sein
+contract-field-json = `{ $field }` ist kein gültiges JSON: { $error }
+
+## JSON viewer
+
+json-search = Schlüssel und Werte durchsuchen
+json-matches = { $count ->
+    [one] Ein Treffer
+   *[other] { $count } Treffer
+}
+json-keys = { $count ->
+    [one] ein Schlüssel
+   *[other] { $count } Schlüssel
+}
+json-items = { $count ->
+    [one] ein Element
+   *[other] { $count } Elemente
+}
+json-copy-path = Pfad kopieren
+json-copy-value = Wert kopieren
+
+## Fonts
+
+fonts-load-ellipsis = Schrift laden…
+fonts-reset = Auf Theme zurücksetzen
+fonts-url = URL:
+fonts-fetch = Abrufen
+fonts-preview = Vorschau:
+fonts-family = Familie: { $family }
+fonts-not-available = (nicht verfügbar)
+fonts-add-fallback = Ersatzschrift hinzufügen…
+fonts-tweaks = Anpassungen
+fonts-font = Schrift
+fonts-scale = Skalierung
+fonts-y-offset-factor = Y-Versatzfaktor
+fonts-y-offset = Y-Versatz
+fonts-loaded = Geladene Schriften
+fonts-delete = Löschen
+fonts-load-failed = Schrift { $name } konnte nicht geladen werden: { $error }
+asset-unknown = Es gibt kein Bild { $id }
+asset-load-failed = Bild { $id } konnte nicht geladen werden: { $error }
+
+## Accessibility
+
+accessibility-ui-scale = UI-Skalierung
+accessibility-browser-zoom = Nutze den Zoom des Browsers
+accessibility-zoom-hint = Strg + und Strg - ändern sie, Strg 0 setzt sie zurück
+accessibility-min-font-size = Minimale Schriftgröße
+accessibility-high-contrast = Hoher Kontrast
+accessibility-reduced-motion = Weniger Bewegung
+accessibility-reset = Zurücksetzen
+
+## Contrast report
+
+contrast-all-pass = Alle Texte in diesem Theme bestehen WCAG AA.
+contrast-failures = { $failures } von { $count } bestehen WCAG AA ({ $ratio }:1) nicht.
+contrast-fails = Nicht bestanden
+contrast-text-on-panels = Text auf Leisten
+contrast-text-in-windows = Text in Fenstern
+contrast-text-in-striped-rows = Text in gestreiften Zeilen
+contrast-code = Code
+contrast-text-fields = Textfelder
+contrast-buttons = Schaltflächen
+contrast-hovered-buttons = Schaltflächen unter dem Mauszeiger
+contrast-pressed-buttons = Gedrückte Schaltflächen
+contrast-links-on-panels = Links auf Leisten
+contrast-links-in-windows = Links in Fenstern
+contrast-warnings = Warnungen
+contrast-errors = Fehler
+
+## Links
+
+link-confirm-title = Diesen Link öffnen?
+link-confirm-text = Dieser Link führt zu einer Seite, die nicht in der Liste vertrauenswürdiger Seiten steht:
+link-trust = Links zu { $domain } immer öffnen
+link-open = Öffnen
+link-cancel = Abbrechen
+link-menu-open = Link öffnen
+link-menu-open-here = In diesem Tab öffnen
+link-menu-copy = Linkadresse kopieren
+links-social-hint = Leere Links werden in der unteren Leiste nicht angezeigt.
+links-allowed = Links immer öffnen zu:
+links-blocked = Links nie öffnen zu:
+links-domains-hint = Eine Domain pro Zeile, einschließlich Subdomains. Bei anderen Seiten wird nachgefragt.
+links-reset = Zurücksetzen
+
+## Log console
+
+log-level = Ausführlichste angezeigte Stufe
+log-target = Ziel
+log-search = Suchen
+log-pause = Anhalten
+log-auto-scroll = Automatisch scrollen
+log-export-ellipsis = Exportieren…
+log-clear = Leeren
+log-count = { $shown } von { $total } Meldungen
+log-empty = Keine Meldungen passen zu den Filtern.
+
+## Help
+
+help-search = Suchen
+help-no-results = Kein Thema enthält „{ $query }“.
+shortcut-help = Hilfe öffnen
+shortcut-close-tour = Rundgang schließen
+shortcut-zoom-in = Vergrößern
+shortcut-zoom-out = Verkleinern
+shortcut-zoom-reset = Originalgröße
+about-version = Version
+about-commit = Commit
+about-build-date = Erstellt am
+about-fonts = Schriftarten
+about-ofl = SIL Open Font License
+
+## Tour
+
+tour-welcome-title = Willkommen
+tour-welcome-text = Dieser kurze Rundgang zeigt dir die App. Du kannst ihn jederzeit beenden und im Hilfe-Menü wiederholen.
+tour-menu-title = Menüleiste
+tour-menu-text = Die Menüs öffnen die anderen Fenster: Stileinstellungen, den Vertrags-Explorer, Schriften und Barrierefreiheit, und hier wählst du die Sprache.
+tour-settings-title = Einstellungen
+tour-settings-text = In dieser Leiste stehen deine Einstellungen. Sie werden beim Schließen der App gespeichert.
+tour-panel-title = Seitenleiste
+tour-panel-text = Noch mehr zum Ausprobieren. Im Ansicht-Menü kannst du beide Seitenleisten aus- und einblenden.
+tour-connect-title = Verbinden
+tour-connect-text = Diese Schaltfläche öffnet die Verbindungsfenster.
+tour-done-title = Das war's
+tour-done-text = Viel Spaß mit der App!
+tour-next = Weiter
+tour-back = Zurück
+tour-skip = Überspringen
+tour-done = Fertig
+tour-dont-show = Nicht mehr anzeigen
+tour-progress = { $step } von { $count }
@@ -0,0 +1,239 @@
+# English, the reference catalog. Every message here must be in the other catalogs too,
+# see `i18n::tests`.
+
+## Menus
+
+menu-file = File
+menu-menu = Menu
+menu-view = View
+menu-help = Help
+menu-language = Language
+save-screenshot-ellipsis = Save Screenshot…
+quit = Quit
+right-side-panel = Right Side Panel
+left-side-panel = Left Side Panel
+help-topics = Help Topics
+keyboard-shortcuts = Keyboard Shortcuts
+whats-new = What's New
+about = About
+take-tour = Take the Tour
+direction-auto = Automatic
+direction-ltr = Left to Right
+direction-rtl = Right to Left
+
+## Windows
+
+style-settings = Style Settings
+contract-explorer = Contract Explorer
+fonts = Fonts
+accessibility = Accessibility
+contrast-report = Contrast Report
+contrast-report-ellipsis = Contrast Report…
+icons = Icons
+links = Links
+log-console = Log Console
+save-screenshot = Save Screenshot
+window = Window
+alert = Alert
+
+## Panels
+
+side-panel = Side Panel
+setting1 = Setting1:{" "}
+setting2 = Setting2:{" "}
+setting3 = Setting3:{" "}
+slider = Slider:{" "}
+panel-button = Button
+collapsing-header = Collapsing Header
+collapsing-label = label
+source-code = Source code.
+
+## Notifications
+
+update-available = Update available — reload
+storage-full = Storage is full, some data could not be saved
+storage-error = Storage error: { $error }
+file-save-failed = Failed to save { $path }: { $error }
+file-download-failed = Download failed: { $error }
+screenshot-saved = Saved screenshot to { $location }
+link-blocked = Blocked a link to { $url }
+log-exported = Saved the log to { $location }
+notification-dismiss = Dismiss
+
+## Screenshots
+
+screenshot-region = Region
+screenshot-whole-app = Whole app
+screenshot-scale = Scale
+screenshot-save = Save
+screenshot-region-hidden = { $region } is no longer shown
+screenshot-region-off-screen = { $region } is not on screen
+screenshot-encode-failed = Failed to encode the screenshot: { $error }
+region-menu-bar = Menu bar
+region-bottom-bar = Bottom bar
+region-left-panel = Left panel
+region-right-panel = Right panel
+region-connect-button = Connect button
+
+## Contract explorer
+
+contract-count = { $count ->
+    [0] No contracts instantiated from this code.
+    [one] One contract instantiated from this code.
+   *[other] { $count } contracts instantiated from this code.
+}
+contract-network = Network:{" "}
+contract-refresh = Refresh
+contract-codes = Codes
+contract-contracts = Contracts
+contract-details = Details
+contract-schema = Schema
+contract-select-code = Select a code id to list its contracts.
+contract-nothing-selected = Nothing selected.
+contract-code-id = Code id
+contract-code-hash = Code hash
+contract-code-creator = Code creator
+contract-address = Address
+contract-label = Label
+contract-admin = Admin
+contract-creator = Creator
+contract-schema-hint = Paste the contract's JSON schema (a single message schema or a full api schema):
+contract-invalid-json = Invalid JSON: { $error }
+contract-message = Message
+contract-no-variants = No message variants found in this schema.
+contract-copy-message = Copy message
+contract-send-hint = Queries are encrypted on Secret; send this with a wallet or secretcli.
+contract-field-required = `{ $field }` is required
+contract-field-integer = `{ $field }` must be an integer
+contract-field-json = `{ $field }` is not valid JSON: { $error }
+
+## JSON viewer
+
+json-search = Search keys and values
+json-matches = { $count ->
+    [one] One match
+   *[other] { $count } matches
+}
+json-keys = { $count ->
+    [one] one key
+   *[other] { $count } keys
+}
+json-items = { $count ->
+    [one] one item
+   *[other] { $count } items
+}
+json-copy-path = Copy path
+json-copy-value = Copy value
+
+## Fonts
+
+fonts-load-ellipsis = Load font…
+fonts-reset = Reset to theme
+fonts-url = URL:
+fonts-fetch = Fetch
+fonts-preview = Preview:
+fonts-family = Family: { $family }
+fonts-not-available = (not available)
+fonts-add-fallback = Add fallback…
+fonts-tweaks = Tweaks
+fonts-font = Font
+fonts-scale = Scale
+fonts-y-offset-factor = Y offset factor
+fonts-y-offset = Y offset
+fonts-loaded = Loaded fonts
+fonts-delete = Delete
+fonts-load-failed = Failed to load font { $name }: { $error }
+asset-unknown = There is no image { $id }
+asset-load-failed = Failed to load image { $id }: { $error }
+
+## Accessibility
+
+accessibility-ui-scale = UI scale
+accessibility-browser-zoom = Use the browser's zoom
+accessibility-zoom-hint = Ctrl + and Ctrl - change it, Ctrl 0 resets it
+accessibility-min-font-size = Minimum font size
+accessibility-high-contrast = High contrast
+accessibility-reduced-motion = Reduced motion
+accessibility-reset = Reset
+
+## Contrast report
+
+contrast-all-pass = All text in this theme passes WCAG AA.
+contrast-failures = { $failures } of { $count } fail WCAG AA ({ $ratio }:1).
+contrast-fails = Fails
+contrast-text-on-panels = Text on panels
+contrast-text-in-windows = Text in windows
+contrast-text-in-striped-rows = Text in striped rows
+contrast-code = Code
+contrast-text-fields = Text fields
+contrast-buttons = Buttons
+contrast-hovered-buttons = Hovered buttons
+contrast-pressed-buttons = Pressed buttons
+contrast-links-on-panels = Links on panels
+contrast-links-in-windows = Links in windows
+contrast-warnings = Warnings
+contrast-errors = Errors
+
+## Links
+
+link-confirm-title = Open this link?
+link-confirm-text = This link goes to a site that isn't on the list of trusted sites:
+link-trust = Always open links to { $domain }
+link-open = Open
+link-cancel = Cancel
+link-menu-open = Open Link
+link-menu-open-here = Open in This Tab
+link-menu-copy = Copy Link Address
+links-social-hint = Links left empty aren't shown in the bottom bar.
+links-allowed = Always open links to:
+links-blocked = Never open links to:
+links-domains-hint = One domain per line. Subdomains are included. Other sites ask first.
+links-reset = Reset
+
+## Log console
+
+log-level = Most verbose level shown
+log-target = Target
+log-search = Search
+log-pause = Pause
+log-auto-scroll = Auto-scroll
+log-export-ellipsis = Export…
+log-clear = Clear
+log-count = { $shown } of { $total } messages
+log-empty = No messages match the filters.
+
+## Help
+
+help-search = Search
+help-no-results = No topics contain “{ $query }”.
+shortcut-help = Open the help
+shortcut-close-tour = Close the tour
+shortcut-zoom-in = Zoom in
+shortcut-zoom-out = Zoom out
+shortcut-zoom-reset = Reset the zoom
+about-version = Version
+about-commit = Commit
+about-build-date = Built on
+about-fonts = Fonts
+about-ofl = SIL Open Font License
+
+## Tour
+
+tour-welcome-title = Welcome
+tour-welcome-text = This short tour shows you around the app. You can leave it at any time and take it again from the Help menu.
+tour-menu-title = Menu bar
+tour-menu-text = The menus open the other windows: style settings, the contract explorer, fonts and accessibility, and let you pick a language.
+tour-settings-title = Settings
+tour-settings-text = This panel holds your settings. They are saved when you close the app.
+tour-panel-title = Side panel
+tour-panel-text = More things to try. The View menu hides and shows both side panels.
+tour-connect-title = Connect
+tour-connect-text = This button opens the connection windows.
+tour-done-title = That's it
+tour-done-text = Enjoy the app!
+tour-next = Next
+tour-back = Back
+tour-skip = Skip
+tour-done = Done
+tour-dont-show = Don't show again
+tour-progress = { $step } of { $count }
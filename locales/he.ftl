@@ -0,0 +1,239 @@
+# Hebrew, shown right to left. Needs a font with Hebrew letters, see View -> Fonts.
+
+## Menus
+
+menu-file = קובץ
+menu-menu = תפריט
+menu-view = תצוגה
+menu-help = עזרה
+menu-language = שפה
+save-screenshot-ellipsis = שמירת צילום מסך…
+quit = יציאה
+right-side-panel = לוח צד ימני
+left-side-panel = לוח צד שמאלי
+help-topics = נושאי עזרה
+keyboard-shortcuts = קיצורי מקלדת
+whats-new = מה חדש
+about = אודות
+take-tour = סיור באפליקציה
+direction-auto = אוטומטי
+direction-ltr = משמאל לימין
+direction-rtl = מימין לשמאל
+
+## Windows
+
+style-settings = הגדרות עיצוב
+contract-explorer = סייר חוזים
+fonts = גופנים
+accessibility = נגישות
+contrast-report = דוח ניגודיות
+contrast-report-ellipsis = דוח ניגודיות…
+icons = סמלים
+links = קישורים
+log-console = יומן
+save-screenshot = שמירת צילום מסך
+window = חלון
+alert = התראה
+
+## Panels
+
+side-panel = לוח צד
+setting1 = הגדרה1:{" "}
+setting2 = הגדרה2:{" "}
+setting3 = הגדרה3:{" "}
+slider = מחוון:{" "}
+panel-button = כפתור
+collapsing-header = כותרת מתקפלת
+collapsing-label = תווית
+source-code = קוד מקור.
+
+## Notifications
+
+update-available = עדכון זמין — טעינה מחדש
+storage-full = האחסון מלא, חלק מהנתונים לא נשמרו
+storage-error = שגיאת אחסון: { $error }
+file-save-failed = שמירת { $path } נכשלה: { $error }
+file-download-failed = ההורדה נכשלה: { $error }
+screenshot-saved = צילום המסך נשמר ב־{ $location }
+link-blocked = נחסם קישור אל { $url }
+log-exported = היומן נשמר ב־{ $location }
+notification-dismiss = סגירה
+
+## Screenshots
+
+screenshot-region = אזור
+screenshot-whole-app = כל האפליקציה
+screenshot-scale = קנה מידה
+screenshot-save = שמירה
+screenshot-region-hidden = { $region } כבר לא מוצג
+screenshot-region-off-screen = { $region } לא נמצא על המסך
+screenshot-encode-failed = קידוד צילום המסך נכשל: { $error }
+region-menu-bar = סרגל התפריטים
+region-bottom-bar = הסרגל התחתון
+region-left-panel = לוח צד שמאלי
+region-right-panel = לוח צד ימני
+region-connect-button = כפתור החיבור
+
+## Contract explorer
+
+contract-count = { $count ->
+    [0] לא נוצרו חוזים מקוד זה.
+    [one] נוצר חוזה אחד מקוד זה.
+    [two] נוצרו שני חוזים מקוד זה.
+   *[other] נוצרו { $count } חוזים מקוד זה.
+}
+contract-network = רשת:{" "}
+contract-refresh = רענון
+contract-codes = קודים
+contract-contracts = חוזים
+contract-details = פרטים
+contract-schema = סכמה
+contract-select-code = יש לבחור מזהה קוד כדי להציג את החוזים שלו.
+contract-nothing-selected = לא נבחר דבר.
+contract-code-id = מזהה קוד
+contract-code-hash = גיבוב הקוד
+contract-code-creator = יוצר הקוד
+contract-address = כתובת
+contract-label = תווית
+contract-admin = מנהל
+contract-creator = יוצר
+contract-schema-hint = יש להדביק את סכמת ה־JSON של החוזה (סכמת הודעה בודדת או סכמת API מלאה):
+contract-invalid-json = JSON לא תקין: { $error }
+contract-message = הודעה
+contract-no-variants = לא נמצאו סוגי הודעות בסכמה הזו.
+contract-copy-message = העתקת ההודעה
+contract-send-hint = שאילתות מוצפנות ב־Secret; יש לשלוח זאת עם ארנק או עם secretcli.
+contract-field-required = `{ $field }` הוא שדה חובה
+contract-field-integer = `{ $field }` חייב להיות מספר שלם
+contract-field-json = `{ $field }` אינו JSON תקין: { $error }
+
+## JSON viewer
+
+json-search = חיפוש במפתחות ובערכים
+json-matches = { $count ->
+    [one] תוצאה אחת
+   *[other] { $count } תוצאות
+}
+json-keys = { $count ->
+    [one] מפתח אחד
+   *[other] { $count } מפתחות
+}
+json-items = { $count ->
+    [one] פריט אחד
+   *[other] { $count } פריטים
+}
+json-copy-path = העתקת הנתיב
+json-copy-value = העתקת הערך
+
+## Fonts
+
+fonts-load-ellipsis = טעינת גופן…
+fonts-reset = איפוס לערכת הנושא
+fonts-url = כתובת:
+fonts-fetch = הורדה
+fonts-preview = תצוגה מקדימה:
+fonts-family = משפחה: { $family }
+fonts-not-available = (לא זמין)
+fonts-add-fallback = הוספת גופן חלופי…
+fonts-tweaks = כוונונים
+fonts-font = גופן
+fonts-scale = קנה מידה
+fonts-y-offset-factor = מקדם היסט אנכי
+fonts-y-offset = היסט אנכי
+fonts-loaded = גופנים שנטענו
+fonts-delete = מחיקה
+fonts-load-failed = טעינת הגופן { $name } נכשלה: { $error }
+asset-unknown = אין תמונה { $id }
+asset-load-failed = טעינת התמונה { $id } נכשלה: { $error }
+
+## Accessibility
+
+accessibility-ui-scale = קנה מידה של הממשק
+accessibility-browser-zoom = יש להשתמש בהגדלה של הדפדפן
+accessibility-zoom-hint = Ctrl + ו־Ctrl - משנים אותו, Ctrl 0 מאפס אותו
+accessibility-min-font-size = גודל גופן מזערי
+accessibility-high-contrast = ניגודיות גבוהה
+accessibility-reduced-motion = הפחתת תנועה
+accessibility-reset = איפוס
+
+## Contrast report
+
+contrast-all-pass = כל הטקסט בערכת הנושא הזו עומד ב־WCAG AA.
+contrast-failures = { $failures } מתוך { $count } לא עומדים ב־WCAG AA ({ $ratio }:1).
+contrast-fails = נכשל
+contrast-text-on-panels = טקסט על לוחות
+contrast-text-in-windows = טקסט בחלונות
+contrast-text-in-striped-rows = טקסט בשורות מפוספסות
+contrast-code = קוד
+contrast-text-fields = שדות טקסט
+contrast-buttons = כפתורים
+contrast-hovered-buttons = כפתורים במעבר עכבר
+contrast-pressed-buttons = כפתורים לחוצים
+contrast-links-on-panels = קישורים על לוחות
+contrast-links-in-windows = קישורים בחלונות
+contrast-warnings = אזהרות
+contrast-errors = שגיאות
+
+## Links
+
+link-confirm-title = לפתוח את הקישור?
+link-confirm-text = הקישור מוביל לאתר שאינו ברשימת האתרים המהימנים:
+link-trust = לפתוח תמיד קישורים אל { $domain }
+link-open = פתיחה
+link-cancel = ביטול
+link-menu-open = פתח קישור
+link-menu-open-here = פתח בכרטיסייה זו
+link-menu-copy = העתק כתובת קישור
+links-social-hint = קישורים ריקים לא מוצגים בסרגל התחתון.
+links-allowed = לפתוח תמיד קישורים אל:
+links-blocked = לא לפתוח אף פעם קישורים אל:
+links-domains-hint = דומיין אחד בכל שורה, כולל תת-דומיינים. באתרים אחרים תופיע שאלה.
+links-reset = איפוס
+
+## Log console
+
+log-level = הרמה המפורטת ביותר שמוצגת
+log-target = יעד
+log-search = חיפוש
+log-pause = השהה
+log-auto-scroll = גלילה אוטומטית
+log-export-ellipsis = ייצוא…
+log-clear = נקה
+log-count = { $shown } מתוך { $total } הודעות
+log-empty = אין הודעות שמתאימות למסננים.
+
+## Help
+
+help-search = חיפוש
+help-no-results = אין נושאים שמכילים את "{ $query }".
+shortcut-help = פתיחת העזרה
+shortcut-close-tour = סגירת הסיור
+shortcut-zoom-in = הגדלה
+shortcut-zoom-out = הקטנה
+shortcut-zoom-reset = איפוס התצוגה
+about-version = גרסה
+about-commit = קומיט
+about-build-date = נבנה בתאריך
+about-fonts = גופנים
+about-ofl = SIL Open Font License
+
+## Tour
+
+tour-welcome-title = ברוכים הבאים
+tour-welcome-text = הסיור הקצר הזה מציג את האפליקציה. אפשר לצאת ממנו בכל רגע ולחזור אליו מתפריט העזרה.
+tour-menu-title = שורת התפריטים
+tour-menu-text = התפריטים פותחים את שאר החלונות: הגדרות סגנון, סייר החוזים, גופנים ונגישות, ובהם בוחרים שפה.
+tour-settings-title = הגדרות
+tour-settings-text = בלוח הזה נמצאות ההגדרות שלך. הן נשמרות כשסוגרים את האפליקציה.
+tour-panel-title = לוח צד
+tour-panel-text = עוד דברים לנסות. בתפריט התצוגה אפשר להסתיר ולהציג את שני לוחות הצד.
+tour-connect-title = התחברות
+tour-connect-text = הכפתור הזה פותח את חלונות ההתחברות.
+tour-done-title = זהו
+tour-done-text = תיהנו מהאפליקציה!
+tour-next = הבא
+tour-back = הקודם
+tour-skip = דלג
+tour-done = סיום
+tour-dont-show = לא להציג שוב
+tour-progress = { $step } מתוך { $count }
@@ -0,0 +1,216 @@
+//! Accessibility settings: UI scale, a minimum font size, high contrast and reduced motion.
+//!
+//! They're applied on top of the theme, so turning one off again restores the theme's look.
+//! On the web the browser controls the zoom, so the UI scale is only used on native.
+
+use egui::gui_zoom::kb_shortcuts;
+use egui::{Context, Slider, Style, Ui};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+
+const SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+const SCALE_STEP: f32 = 0.1;
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Accessibility {
+    /// Multiplies the platform's pixels per point.
+    pub ui_scale: f32,
+    /// No text style is smaller than this, in points.
+    pub min_font_size: f32,
+    pub high_contrast: bool,
+    /// No animations, e.g. when opening panels and collapsing headers.
+    pub reduced_motion: bool,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            min_font_size: 10.0, // the smallest size in `my_style`
+            high_contrast: false,
+            reduced_motion: false,
+        }
+    }
+}
+
+impl Accessibility {
+    /// `theme` with these settings applied.
+    pub fn style(&self, theme: &Style) -> Style {
+        let mut style = theme.clone();
+        for font_id in style.text_styles.values_mut() {
+            font_id.size = font_id.size.max(self.min_font_size);
+        }
+        if self.high_contrast {
+            style.visuals = crate::style::my_high_contrast_visuals();
+        }
+        if self.reduced_motion {
+            style.animation_time = 0.0;
+        }
+        style
+    }
+
+    /// The theme under `current`, a style these settings were applied to `theme` for, with
+    /// the changes made to it since, e.g. in the Style Settings window. Apply other settings
+    /// on top of this one, so the changes stay.
+    pub fn theme_under(&self, theme: &Style, current: &Style) -> Style {
+        let applied = self.style(theme);
+        let mut under = current.clone();
+        for (text_style, font_id) in &mut under.text_styles {
+            if applied.text_styles.get(text_style) == Some(font_id) {
+                if let Some(original) = theme.text_styles.get(text_style) {
+                    *font_id = original.clone();
+                }
+            }
+        }
+        if under.visuals == applied.visuals {
+            under.visuals = theme.visuals.clone();
+        }
+        // Changed all the time, see `crate::style::change_animation_time`.
+        under.animation_time = theme.animation_time;
+        under
+    }
+
+    /// Sets the style of `ctx` to `theme` with these settings applied.
+    pub fn apply(&self, ctx: &Context, theme: &Style) {
+        ctx.set_style(self.style(theme));
+        let reduced_motion = self.reduced_motion;
+        ctx.data_mut(|data| data.insert_temp(reduced_motion_id(), reduced_motion));
+    }
+
+    /// Sets the pixels per point from the UI scale. Does nothing on the web.
+    pub fn apply_scale(&self, ctx: &Context, native_pixels_per_point: Option<f32>) {
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+        ctx.set_pixels_per_point(native_pixels_per_point.unwrap_or(1.0) * self.ui_scale);
+    }
+
+    /// Ctrl +, Ctrl - and Ctrl 0 change the UI scale, like in a browser.
+    pub fn handle_shortcuts(&mut self, ctx: &Context) {
+        let scale = if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_RESET)) {
+            1.0
+        } else if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_IN)) {
+            self.ui_scale + SCALE_STEP
+        } else if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_OUT)) {
+            self.ui_scale - SCALE_STEP
+        } else {
+            return;
+        };
+        // Rounded, so a few steps in and out get back to exactly 1.0.
+        self.ui_scale =
+            ((scale * 10.0).round() / 10.0).clamp(*SCALE_RANGE.start(), *SCALE_RANGE.end());
+    }
+
+    /// Returns `true` if a setting changed.
+    pub fn ui(&mut self, ui: &mut Ui, t: &Translator) -> bool {
+        let before = self.clone();
+        egui::Grid::new("accessibility_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.add(BidiLabel::new(t.get("accessibility-ui-scale")));
+                if cfg!(target_arch = "wasm32") {
+                    ui.add(BidiLabel::new(t.get("accessibility-browser-zoom")));
+                } else {
+                    ui.add(Slider::new(&mut self.ui_scale, SCALE_RANGE).step_by(SCALE_STEP as f64))
+                        .on_hover_text(visual_order(&t.get("accessibility-zoom-hint")));
+                }
+                ui.end_row();
+
+                ui.add(BidiLabel::new(t.get("accessibility-min-font-size")));
+                ui.add(Slider::new(&mut self.min_font_size, 8.0..=24.0).suffix(" pt"));
+                ui.end_row();
+            });
+        ui.checkbox(
+            &mut self.high_contrast,
+            visual_order(&t.get("accessibility-high-contrast")),
+        );
+        ui.checkbox(
+            &mut self.reduced_motion,
+            visual_order(&t.get("accessibility-reduced-motion")),
+        );
+
+        ui.add_space(4.0);
+        if ui
+            .button(visual_order(&t.get("accessibility-reset")))
+            .clicked()
+        {
+            *self = Self::default();
+        }
+        *self != before
+    }
+}
+
+fn reduced_motion_id() -> egui::Id {
+    egui::Id::new("reduced_motion")
+}
+
+/// Whether animations are turned off in `ctx`, see [`crate::style::change_animation_time`].
+pub fn reduced_motion(ctx: &Context) -> bool {
+    ctx.data_mut(|data| data.get_temp(reduced_motion_id()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::TextStyle;
+
+    #[test]
+    fn settings_apply_on_top_of_the_theme() {
+        let theme = crate::style::my_style();
+        let settings = Accessibility {
+            min_font_size: 16.0,
+            high_contrast: true,
+            reduced_motion: true,
+            ..Default::default()
+        };
+        let ctx = Context::default();
+        settings.apply(&ctx, &theme);
+
+        let style = ctx.style();
+        assert_eq!(style.text_styles[&TextStyle::Small].size, 16.0);
+        assert_eq!(style.text_styles[&TextStyle::Heading].size, 18.0);
+        assert_ne!(style.visuals, theme.visuals);
+
+        crate::style::change_animation_time(&ctx, 0.3);
+        assert_eq!(ctx.style().animation_time, 0.0);
+
+        Accessibility::default().apply(&ctx, &theme);
+        assert_eq!(*ctx.style(), theme);
+        crate::style::change_animation_time(&ctx, 0.3);
+        assert_eq!(ctx.style().animation_time, 0.3);
+    }
+
+    #[test]
+    fn changes_to_the_style_stay() {
+        let theme = crate::style::my_style();
+        let ctx = Context::default();
+        let before = Accessibility {
+            min_font_size: 16.0,
+            ..Default::default()
+        };
+        before.apply(&ctx, &theme);
+
+        // As in the Style Settings window.
+        let mut edited = (*ctx.style()).clone();
+        edited.spacing.item_spacing = egui::vec2(20.0, 20.0);
+        edited.text_styles.get_mut(&TextStyle::Body).unwrap().size = 30.0;
+        ctx.set_style(edited);
+
+        let after = Accessibility {
+            high_contrast: true,
+            ..Default::default()
+        };
+        let theme = before.theme_under(&theme, &ctx.style());
+        after.apply(&ctx, &theme);
+        let style = ctx.style();
+        assert_eq!(style.spacing.item_spacing, egui::vec2(20.0, 20.0));
+        assert_eq!(style.text_styles[&TextStyle::Body].size, 30.0);
+        // The minimum font size is gone again.
+        let small = crate::style::my_style().text_styles[&TextStyle::Small].size;
+        assert_eq!(style.text_styles[&TextStyle::Small].size, small);
+        assert_eq!(style.visuals, crate::style::my_high_contrast_visuals());
+    }
+}
@@ -1,12 +1,50 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use eframe::egui;
 
 #[allow(unused_imports)]
 use egui::{
-    vec2, Align2, Area, CentralPanel, Color32, Frame, Margin, RichText, Rounding, SidePanel,
-    TopBottomPanel, Vec2, Window,
+    panel::Side, vec2, Align2, Area, CentralPanel, Color32, Frame, Margin, RichText, Rounding,
+    SidePanel, TopBottomPanel, Vec2, Window,
 };
 
+use crate::accessibility::Accessibility;
+use crate::assets::Assets;
+use crate::contracts::ContractExplorer;
+use crate::direction::{visual_order, BidiLabel, Direction, DirectionSetting};
+use crate::fonts::FontManager;
+use crate::help::{Help, Page};
+use crate::i18n::{Language, Translator};
+use crate::icons::{Icon, IconButton};
+use crate::links::{CustomHyperlink, LinkSettings, Links};
+use crate::logs::{LogConsole, Logs};
+use crate::network::Network;
+use crate::notifications::{Notification, NotificationAction, Notifications};
+use crate::pwa::UpdateWatcher;
+use crate::router::{Route, Router};
+use crate::screenshot::{Region, Screenshot};
+use crate::storage::{Storage, StorageError};
 use crate::style::*;
+use crate::tour::Tour;
+
+/// How the app starts, e.g. from command line arguments on native.
+#[derive(Default)]
+pub struct AppOptions {
+    /// Used instead of [`crate::style::my_style`].
+    pub style: Option<egui::Style>,
+    /// What to show first. On the web this comes from the page URL instead.
+    pub route: Option<Route>,
+    /// Overrides the network remembered from last time.
+    pub network: Option<Network>,
+    /// Keep the app state and data in this directory instead of the platform's data
+    /// directory. Only used on native.
+    pub storage_dir: Option<PathBuf>,
+    /// Start with the default state instead of the one saved last time.
+    pub reset_state: bool,
+    /// The events for the log console. `main.rs` adds their layer to the tracing subscriber.
+    pub logs: Logs,
+}
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -16,14 +54,51 @@ pub struct TemplateApp {
     setting2: String,
     setting3: String,
     slider_value: u8,
-    #[serde(skip)]
-    svg_image: egui_extras::RetainedImage,
-    welcome_window_open: bool,
+    tour: Tour,
+    help: Help,
     connect_window_open: bool,
     alert_window_open: bool,
     right_panel_open: bool,
     left_panel_open: bool,
     style_window_open: bool,
+    network: Network,
+    contract_explorer: ContractExplorer,
+    contract_explorer_open: bool,
+    screenshot: Screenshot,
+    screenshot_window_open: bool,
+    fonts: FontManager,
+    fonts_window_open: bool,
+    accessibility: Accessibility,
+    accessibility_window_open: bool,
+    contrast_window_open: bool,
+    icons_window_open: bool,
+    links: LinkSettings,
+    links_window_open: bool,
+    log_window_open: bool,
+    language: Language,
+    layout_direction: DirectionSetting,
+    /// The style before the accessibility settings are applied.
+    #[serde(skip)]
+    theme: Arc<egui::Style>,
+    #[serde(skip)]
+    log_console: LogConsole,
+    #[serde(skip)]
+    notifications: Notifications,
+    #[serde(skip)]
+    update_watcher: UpdateWatcher,
+    #[serde(skip)]
+    storage: Storage,
+    #[serde(skip)]
+    router: Router,
+    /// Where the state is saved when it isn't kept in eframe's storage.
+    #[serde(skip)]
+    state_file: Option<PathBuf>,
+    /// File->Quit was clicked; the window is closed at the end of the frame.
+    #[serde(skip)]
+    quit_requested: bool,
+    /// Panels and windows shown this frame, which can be captured on their own.
+    #[serde(skip)]
+    regions: Vec<Region>,
 }
 
 impl Default for TemplateApp {
@@ -33,109 +108,407 @@ impl Default for TemplateApp {
             setting2: "customizable value".to_owned(),
             setting3: "customizable value".to_owned(),
             slider_value: 50u8,
-            svg_image: egui_extras::RetainedImage::from_svg_bytes_with_size(
-                "scrt.svg",
-                include_bytes!("../assets/scrt.svg"),
-                egui_extras::image::FitTo::Original,
-            )
-            .unwrap(),
-            welcome_window_open: true,
+            tour: Tour::default(),
+            help: Help::default(),
             connect_window_open: false,
             alert_window_open: false,
             right_panel_open: true,
             left_panel_open: true,
             style_window_open: false,
+            network: Network::default(),
+            contract_explorer: ContractExplorer::default(),
+            contract_explorer_open: false,
+            screenshot: Screenshot::default(),
+            screenshot_window_open: false,
+            fonts: FontManager::default(),
+            fonts_window_open: false,
+            accessibility: Accessibility::default(),
+            accessibility_window_open: false,
+            contrast_window_open: false,
+            icons_window_open: false,
+            links: LinkSettings::default(),
+            links_window_open: false,
+            log_window_open: false,
+            language: Language::default(),
+            layout_direction: DirectionSetting::default(),
+            theme: Arc::new(crate::style::my_style()),
+            log_console: LogConsole::default(),
+            notifications: Notifications::default(),
+            update_watcher: UpdateWatcher::default(),
+            storage: Storage::default(),
+            router: Router::default(),
+            state_file: None,
+            quit_requested: false,
+            regions: Vec::new(),
         }
     }
 }
 
 impl TemplateApp {
     /// Called once before the first frame.
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // This is also where you can customize the look and feel of egui using
-        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
-        cc.egui_ctx.set_style(crate::style::my_style());
-        cc.egui_ctx.set_fonts(crate::style::my_font_definitions());
+    pub fn new(cc: &eframe::CreationContext<'_>, options: AppOptions) -> Self {
+        configure_context(&cc.egui_ctx, options.style);
+
+        #[cfg(target_arch = "wasm32")]
+        let storage_dir: Option<PathBuf> = None;
+        #[cfg(not(target_arch = "wasm32"))]
+        let storage_dir = options.storage_dir;
+        let state_file = storage_dir.as_ref().map(|dir| dir.join("app.json"));
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+        let mut app: Self = if options.reset_state {
+            Self::default()
+        } else if let Some(path) = &state_file {
+            load_state_file(path).unwrap_or_default()
+        } else {
+            cc.storage
+                .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+                .unwrap_or_default()
+        };
+
+        app.update_watcher = UpdateWatcher::start(&cc.egui_ctx);
+        app.storage = match &storage_dir {
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(dir) => Storage::new(crate::storage::FileStore::new(dir.join("data"))),
+            _ => Storage::platform_default(),
+        };
+        app.state_file = state_file;
+        app.log_console = LogConsole::new(options.logs);
+        app.fonts.start(&cc.egui_ctx, &app.storage);
+        app.theme = cc.egui_ctx.style();
+        app.accessibility.apply(&cc.egui_ctx, &app.theme);
+        Translator::new(app.language).install(&cc.egui_ctx);
+        app.router = Router::new(&cc.egui_ctx);
+        if let Some(route) = options.route {
+            app.router.request(route);
+        }
+        if let Some(network) = options.network {
+            app.network = network;
+        }
+
+        app
+    }
+
+    /// The route matching what is currently shown.
+    fn route(&self) -> Route {
+        if self.contract_explorer_open {
+            match self.contract_explorer.selected_address() {
+                Some(address) => Route::Contract(address),
+                None => Route::Contracts,
+            }
+        } else if self.style_window_open {
+            Route::Settings
+        } else {
+            Route::Home
         }
+    }
+
+    fn apply_route(&mut self, route: Route) {
+        self.contract_explorer_open = matches!(route, Route::Contracts | Route::Contract(_));
+        self.style_window_open = route == Route::Settings;
+        match route {
+            Route::Contract(address) => self.contract_explorer.open_contract(address),
+            _ => self.contract_explorer.clear_selection(),
+        }
+    }
+}
+
+/// This is also where you can customize the look and feel of egui using
+/// `ctx.set_visuals` and `ctx.set_fonts`.
+pub(crate) fn configure_context(ctx: &egui::Context, style: Option<egui::Style>) {
+    ctx.set_style(style.unwrap_or_else(crate::style::my_style));
+    ctx.set_fonts(crate::style::my_font_definitions());
+    crate::style::my_text_styles().install(ctx);
+}
+
+fn load_state_file(path: &Path) -> Option<TemplateApp> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text)
+        .map_err(|err| tracing::warn!("ignoring {}: {err}", path.display()))
+        .ok()
+}
 
-        Default::default()
+fn save_state_file(path: &Path, app: &TemplateApp) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(path, serde_json::to_string_pretty(app).unwrap()));
+    if let Err(err) = result {
+        tracing::warn!("failed to save {}: {err}", path.display());
     }
 }
 
 impl eframe::App for TemplateApp {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+        match &self.state_file {
+            Some(path) => save_state_file(path, self),
+            None => eframe::set_value(storage, eframe::APP_KEY, self),
+        }
     }
 
     /// Called each time the UI needs repainting, which may be many times per second.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.accessibility
+            .apply_scale(ctx, frame.info().native_pixels_per_point);
+        self.ui(ctx);
+        if std::mem::take(&mut self.quit_requested) {
+            frame.close();
+        }
+    }
+}
+
+impl TemplateApp {
+    /// Shows the whole app. Unlike [`eframe::App::update`] this doesn't need a window,
+    /// so tests can run it on a headless `egui::Context`.
+    pub fn ui(&mut self, ctx: &egui::Context) {
+        self.handle_events(ctx);
+        self.show(ctx);
+        if let Some(request) = self.screenshot.take_request() {
+            self.save_screenshot(ctx, &request);
+        }
+        let route = self.route();
+        self.router.navigated(route);
+    }
+
+    /// What happens once per frame besides showing the app: routes, shortcuts, and the
+    /// news from background work turned into notifications.
+    fn handle_events(&mut self, ctx: &egui::Context) {
+        if let Some(route) = self.router.take_requested() {
+            self.apply_route(route);
+        }
+        let t = Translator::load(ctx);
+        t.check_fonts(ctx);
+        let notifications = &mut self.notifications;
+
+        if self.update_watcher.take_update_available() {
+            notifications.push(
+                Notification::info(t.get("update-available"))
+                    .with_action(NotificationAction::Reload),
+            );
+        }
+        #[cfg(not(target_arch = "wasm32"))] // the browser zooms on the web
+        self.accessibility.handle_shortcuts(ctx);
+        self.help.update(ctx);
+        self.tour.update(ctx);
+        self.fonts.update(ctx, &self.storage);
+        for (name, err) in self.fonts.take_errors() {
+            let args = [("name", (&name).into()), ("error", (&err).into())];
+            notifications.push(Notification::error(t.format("fonts-load-failed", &args)));
+        }
+        for (id, err) in Assets::load(ctx).take_errors() {
+            notifications.push(Notification::error(err.message(&t, &id)));
+        }
+        let link_handler = Links::load(ctx);
+        link_handler.set_policy(&self.links.policy);
+        for url in link_handler.take_blocked() {
+            notifications.push(Notification::warning(
+                t.format("link-blocked", &[("url", (&url).into())]),
+            ));
+        }
+        for err in self.storage.take_errors() {
+            notifications.push(match err {
+                StorageError::QuotaExceeded => Notification::error(t.get("storage-full")),
+                StorageError::Other(err) => {
+                    Notification::warning(t.format("storage-error", &[("error", (&err).into())]))
+                }
+            });
+        }
+        match self.screenshot.take_saved() {
+            Some(Ok(Some(location))) => notifications.push(Notification::info(
+                t.format("screenshot-saved", &[("location", (&location).into())]),
+            )),
+            Some(Err(err)) => notifications.push(Notification::error(err.message(&t))),
+            Some(Ok(None)) | None => {}
+        }
+        if let Some(notification) = self.log_console.take_notification(&t) {
+            notifications.push(notification);
+        }
+    }
+
+    /// Lays out the app and reacts to the input. Everything else is in
+    /// [`Self::handle_events`], so [`Self::capture`] can run this again on another context.
     /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn show(&mut self, ctx: &egui::Context) {
         let Self {
             setting1,
             setting2,
             setting3,
             slider_value,
-            svg_image: _,
-            welcome_window_open,
+            tour,
+            help,
             connect_window_open,
             alert_window_open,
             right_panel_open,
             left_panel_open,
             style_window_open,
+            network,
+            contract_explorer,
+            contract_explorer_open,
+            screenshot: _,
+            screenshot_window_open,
+            fonts,
+            fonts_window_open,
+            accessibility,
+            accessibility_window_open,
+            contrast_window_open,
+            icons_window_open,
+            links,
+            links_window_open,
+            log_window_open,
+            language,
+            layout_direction,
+            theme,
+            log_console,
+            notifications,
+            update_watcher,
+            storage,
+            router: _,
+            state_file: _,
+            quit_requested,
+            regions,
         } = self;
+        regions.clear();
+        let t = Translator::load(ctx);
+        let direction = layout_direction.resolve(t.displayed_language());
+        direction.store(ctx);
+        // The panel toggles are named after where the panels are.
+        let (left_panel_label, right_panel_label) = match direction {
+            Direction::LeftToRight => ("left-side-panel", "right-side-panel"),
+            Direction::RightToLeft => ("right-side-panel", "left-side-panel"),
+        };
+        let left_panel_label = visual_order(&t.get(left_panel_label)).into_owned();
+        let right_panel_label = visual_order(&t.get(right_panel_label)).into_owned();
+        let (left_panel_icon, right_panel_icon) = match direction {
+            Direction::LeftToRight => (Icon::PanelLeft, Icon::PanelRight),
+            Direction::RightToLeft => (Icon::PanelRight, Icon::PanelLeft),
+        };
+        #[cfg(target_arch = "wasm32")] // no File->Quit on web pages!
+        let _ = quit_requested;
+        let link_handler = Links::load(ctx);
+        let mut notification_action = None;
 
         #[cfg(not(target_arch = "wasm32"))] // no File->Quit on web pages!
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
             egui::menu::bar(ui, |ui| {
-                // egui::widgets::global_dark_light_mode_switch(ui);
-                ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
-                ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
-                ui.menu_button("File", |ui| {
-                    ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
-                    ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
-                    if ui.button("Quit").clicked() {
-                        _frame.close();
-                    };
-                });
-                ui.menu_button("View", |ui| {
+                direction.horizontal(ui, |ui| {
+                    // egui::widgets::global_dark_light_mode_switch(ui);
                     ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
                     ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
-                    if ui.button("Right Side Panel").clicked() {
-                        *right_panel_open = !*right_panel_open;
-                    }
-                    if ui.button("Left Side Panel").clicked() {
-                        *left_panel_open = !*left_panel_open;
-                    }
-                    if ui.button("Style Settings").clicked() {
-                        *style_window_open = true;
-                        ui.close_menu();
-                    }
-                });
-                ui.menu_button("Help", |ui| {
-                    ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
-                    ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
-                    if ui.button("Button").clicked() {
-                        // ...
-                    }
+                    ui.menu_button(visual_order(&t.get("menu-file")), |ui| {
+                        ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
+                        ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Camera)
+                                    .text(visual_order(&t.get("save-screenshot-ellipsis"))),
+                            )
+                            .clicked()
+                        {
+                            *screenshot_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(IconButton::new(Icon::Quit).text(visual_order(&t.get("quit"))))
+                            .clicked()
+                        {
+                            *quit_requested = true;
+                        };
+                    });
+                    ui.menu_button(visual_order(&t.get("menu-view")), |ui| {
+                        ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
+                        ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
+                        if ui
+                            .add(IconButton::new(right_panel_icon).text(&right_panel_label))
+                            .clicked()
+                        {
+                            *right_panel_open = !*right_panel_open;
+                        }
+                        if ui
+                            .add(IconButton::new(left_panel_icon).text(&left_panel_label))
+                            .clicked()
+                        {
+                            *left_panel_open = !*left_panel_open;
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Palette)
+                                    .text(visual_order(&t.get("style-settings"))),
+                            )
+                            .clicked()
+                        {
+                            *style_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Contract)
+                                    .text(visual_order(&t.get("contract-explorer"))),
+                            )
+                            .clicked()
+                        {
+                            *contract_explorer_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(IconButton::new(Icon::Font).text(visual_order(&t.get("fonts"))))
+                            .clicked()
+                        {
+                            *fonts_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Accessibility)
+                                    .text(visual_order(&t.get("accessibility"))),
+                            )
+                            .clicked()
+                        {
+                            *accessibility_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(IconButton::new(Icon::Link).text(visual_order(&t.get("links"))))
+                            .clicked()
+                        {
+                            *links_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Terminal)
+                                    .text(visual_order(&t.get("log-console"))),
+                            )
+                            .clicked()
+                        {
+                            *log_window_open = true;
+                            ui.close_menu();
+                        }
+                        if cfg!(debug_assertions)
+                            && ui.button(visual_order(&t.get("icons"))).clicked()
+                        {
+                            *icons_window_open = true;
+                            ui.close_menu();
+                        }
+                        language_menu(ui, &t, language, layout_direction);
+                    });
+                    ui.menu_button(visual_order(&t.get("menu-help")), |ui| {
+                        ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
+                        ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
+                        help_menu(ui, &t, help, tour);
+                    });
                 });
             });
             Area::new("notification")
-                .anchor(Align2::RIGHT_TOP, Vec2::new(-12.0, 1.0))
+                .anchor(
+                    direction.anchor(Align2::RIGHT_TOP),
+                    direction.offset(Vec2::new(-12.0, 1.0)),
+                )
                 .show(ctx, |ui| {
                     ui.style_mut().wrap = Some(false);
-                    ui.style_mut().visuals.hyperlink_color = Color32::from_rgb(242, 176, 70);
-                    ui.label(
-                        RichText::new("Notifications could show up here")
-                            .color(Color32::from_rgb(242, 176, 70))
-                            .italics(),
-                    );
+                    notification_action = notifications.ui(ui);
                 });
         });
 
@@ -145,69 +518,148 @@ impl eframe::App for TemplateApp {
             ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
             ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
             egui::menu::bar(ui, |ui| {
-                egui::widgets::global_dark_light_mode_switch(ui);
-                ui.menu_button("Menu", |ui| {
-                    ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
-                    ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
-                    if ui.button("Welcome Message").clicked() {
-                        *welcome_window_open = true;
-                        ui.close_menu();
-                    }
-                    if ui.button("Style Settings").clicked() {
-                        *style_window_open = true;
-                        ui.close_menu();
-                    }
-                });
-                ui.menu_button("View", |ui| {
-                    ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
-                    ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
-                    if ui.button("Right Side Panel").clicked() {
-                        *right_panel_open = !*right_panel_open;
-                    }
-                    if ui.button("Left Side Panel").clicked() {
-                        *left_panel_open = !*left_panel_open;
-                    }
+                direction.horizontal(ui, |ui| {
+                    egui::widgets::global_dark_light_mode_switch(ui);
+                    ui.menu_button(visual_order(&t.get("menu-menu")), |ui| {
+                        ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
+                        ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
+                        ui.menu_button(visual_order(&t.get("menu-help")), |ui| {
+                            help_menu(ui, &t, help, tour);
+                        });
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Palette)
+                                    .text(visual_order(&t.get("style-settings"))),
+                            )
+                            .clicked()
+                        {
+                            *style_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Camera)
+                                    .text(visual_order(&t.get("save-screenshot-ellipsis"))),
+                            )
+                            .clicked()
+                        {
+                            *screenshot_window_open = true;
+                            ui.close_menu();
+                        }
+                    });
+                    ui.menu_button(visual_order(&t.get("menu-view")), |ui| {
+                        ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
+                        ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
+                        if ui
+                            .add(IconButton::new(right_panel_icon).text(&right_panel_label))
+                            .clicked()
+                        {
+                            *right_panel_open = !*right_panel_open;
+                        }
+                        if ui
+                            .add(IconButton::new(left_panel_icon).text(&left_panel_label))
+                            .clicked()
+                        {
+                            *left_panel_open = !*left_panel_open;
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Contract)
+                                    .text(visual_order(&t.get("contract-explorer"))),
+                            )
+                            .clicked()
+                        {
+                            *contract_explorer_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(IconButton::new(Icon::Font).text(visual_order(&t.get("fonts"))))
+                            .clicked()
+                        {
+                            *fonts_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Accessibility)
+                                    .text(visual_order(&t.get("accessibility"))),
+                            )
+                            .clicked()
+                        {
+                            *accessibility_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(IconButton::new(Icon::Link).text(visual_order(&t.get("links"))))
+                            .clicked()
+                        {
+                            *links_window_open = true;
+                            ui.close_menu();
+                        }
+                        if ui
+                            .add(
+                                IconButton::new(Icon::Terminal)
+                                    .text(visual_order(&t.get("log-console"))),
+                            )
+                            .clicked()
+                        {
+                            *log_window_open = true;
+                            ui.close_menu();
+                        }
+                        if cfg!(debug_assertions)
+                            && ui.button(visual_order(&t.get("icons"))).clicked()
+                        {
+                            *icons_window_open = true;
+                            ui.close_menu();
+                        }
+                        language_menu(ui, &t, language, layout_direction);
+                    });
                 });
             });
             Area::new("notification")
-                .anchor(Align2::RIGHT_TOP, Vec2::new(-12.0, 1.0))
+                .anchor(
+                    direction.anchor(Align2::RIGHT_TOP),
+                    direction.offset(Vec2::new(-12.0, 1.0)),
+                )
                 .show(ctx, |ui| {
                     ui.style_mut().wrap = Some(false);
-                    ui.style_mut().visuals.hyperlink_color = Color32::from_rgb(242, 176, 70);
-                    ui.link(
-                        RichText::new("Notifications could show up here")
-                            .color(Color32::from_rgb(242, 176, 70))
-                            .italics(),
-                    );
+                    notification_action = notifications.ui(ui);
                 });
         });
 
+        match notification_action {
+            Some(NotificationAction::Reload) => update_watcher.apply(),
+            None => {}
+        }
+
         TopBottomPanel::bottom("bottom_panel")
             .exact_height(26.0)
             .show(ctx, |ui| {
-                ui.horizontal(|ui| {
+                direction.horizontal(ui, |ui| {
                     // ui.hyperlink("https://github.com/kent-3/egui-experiments");
-                    ui.add(egui::github_link_file!(
-                        "https://github.com/kent-3/egui-experiments/blob/master/",
-                        "Source code."
+                    ui.add(CustomHyperlink::from_label_and_url(
+                        visual_order(&t.get("source-code")),
+                        concat!(
+                            "https://github.com/kent-3/egui-experiments/blob/master/",
+                            file!()
+                        ),
                     ));
                     egui::warn_if_debug_build(ui);
-                    ui.small("Use ctrl+shift+R to reset page")
                     // egui::widgets::global_dark_light_mode_buttons(ui);
                 });
                 Area::new("my_area")
-                    .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-8.0, -4.0))
+                    .anchor(
+                        direction.anchor(Align2::RIGHT_BOTTOM),
+                        direction.offset(Vec2::new(-8.0, -4.0)),
+                    )
                     .show(ctx, |ui| {
                         ui.horizontal(|ui| {
-                            ui.add(CustomHyperlink::from_label_and_url(
-                                "Discord",
-                                "about:blank",
-                            ));
-                            ui.add(CustomHyperlink::from_label_and_url(
-                                "Twitter",
-                                "about:blank",
-                            ));
-                            ui.add(CustomHyperlink::from_label_and_url("GitHub", "about:blank"));
+                            for (name, url) in links.social() {
+                                ui.add(
+                                    CustomHyperlink::from_label_and_url(name, url)
+                                        .external_icon(true),
+                                );
+                            }
                             // egui::widgets::global_dark_light_mode_switch(ui);
                         });
                     });
@@ -215,39 +667,41 @@ impl eframe::App for TemplateApp {
 
         change_animation_time(ctx, 1.0 / 6.0); // slower animation for panels looks nice
 
-        SidePanel::left("left_panel")
+        SidePanel::new(direction.side(Side::Left), "left_panel")
             .min_width(350.0)
             .frame(
                 Frame::side_top_panel(&ctx.style()).inner_margin(Margin::same(10.0)), // .fill(egui::Color32::from_rgb(45, 47, 49)),
             )
             .show_animated(ctx, *left_panel_open, |ui| {
-                ui.heading("Side Panel");
-                ui.separator();
-                ui.add_space(10.0);
+                ui.with_layout(direction.vertical(), |ui| {
+                    ui.add(BidiLabel::new(RichText::new(t.get("side-panel")).heading()));
+                    ui.separator();
+                    ui.add_space(10.0);
 
-                ui.style_mut().visuals.widgets.inactive.rounding = Rounding::same(0.0);
-                ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
-                ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
+                    ui.style_mut().visuals.widgets.inactive.rounding = Rounding::same(0.0);
+                    ui.style_mut().visuals.widgets.hovered.rounding = Rounding::same(0.0);
+                    ui.style_mut().visuals.widgets.active.rounding = Rounding::same(0.0);
 
-                ui.horizontal(|ui| {
-                    ui.label("Setting1: ");
-                    ui.text_edit_singleline(setting1);
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Setting2: ");
-                    ui.text_edit_singleline(setting2);
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Setting3: ");
-                    ui.text_edit_singleline(setting3);
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Slider: ");
-                    ui.add(egui::Slider::new(slider_value, 0..=100));
+                    direction.horizontal(ui, |ui| {
+                        ui.add(BidiLabel::new(t.get("setting1")));
+                        ui.text_edit_singleline(setting1);
+                    });
+                    direction.horizontal(ui, |ui| {
+                        ui.add(BidiLabel::new(t.get("setting2")));
+                        ui.text_edit_singleline(setting2);
+                    });
+                    direction.horizontal(ui, |ui| {
+                        ui.add(BidiLabel::new(t.get("setting3")));
+                        ui.text_edit_singleline(setting3);
+                    });
+                    direction.horizontal(ui, |ui| {
+                        ui.add(BidiLabel::new(t.get("slider")));
+                        ui.add(egui::Slider::new(slider_value, 0..=100));
+                    });
                 });
             });
 
-        SidePanel::right("right_panel")
+        SidePanel::new(direction.side(Side::Right), "right_panel")
             .resizable(true)
             .frame(
                 Frame::side_top_panel(&ctx.style()).inner_margin(Margin::same(10.0)), // .fill(egui::Color32::from_rgb(45, 47, 49)),
@@ -255,23 +709,25 @@ impl eframe::App for TemplateApp {
             .max_width(200.0)
             .show_animated(ctx, *right_panel_open, |ui| {
                 ui.vertical_centered(|ui| {
-                    ui.heading("Side Panel");
+                    ui.add(BidiLabel::new(RichText::new(t.get("side-panel")).heading()));
                     ui.separator();
                     ui.add_space(4.0);
-                    if ui.button("Button").clicked() {
+                    let response = ui.button(visual_order(&t.get("panel-button")));
+                    if response.clicked() {
                         *connect_window_open = true;
                         *alert_window_open = true;
                     };
+                    regions.push(("Connect button", response.rect));
                     ui.add_space(4.0);
 
                     change_animation_time(ctx, 0.30);
-                    ui.collapsing("Collapsing Header", |ui| {
-                        ui.label("label");
-                        ui.label("label");
-                        ui.label("label");
-                        ui.label("label");
-                        ui.label("label");
-                    });
+                    egui::CollapsingHeader::new(visual_order(&t.get("collapsing-header")))
+                        .id_source("Collapsing Header")
+                        .show(ui, |ui| {
+                            for _ in 0..5 {
+                                ui.add(BidiLabel::new(t.get("collapsing-label")));
+                            }
+                        });
                     reset_animation_time(ctx);
 
                     ui.add_space(4.0);
@@ -280,43 +736,103 @@ impl eframe::App for TemplateApp {
 
         reset_animation_time(ctx);
 
+        let central_rect = ctx.available_rect();
         CentralPanel::default().show(ctx, |ui| {
             // The central panel is the region left after adding TopPanels and SidePanels
+            my_background(ui.visuals()).paint(ui, central_rect);
 
             // Uncomment to add a window to experiment with style settings
-            Window::new("Style Settings")
+            let response = Window::new(visual_order(&t.get("style-settings")))
+                .id(egui::Id::new("Style Settings"))
                 .open(style_window_open)
                 .scroll2([true, true])
                 .show(ctx, |ui| {
                     ctx.style_ui(ui);
                 });
+            record_window(regions, "Style Settings", response);
 
-            // Area::new("background_image")
-            //     .anchor(Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
-            //     .order(egui::Order::Background)
-            //     .show(ctx, |ui| {
-            //         svg_image.show_max_size(ui, egui::Vec2 { x: 400.0, y: 400.0 });
-            //     });
-
-            Window::new("Welcome Message")
-                .resizable(true)
-                .open(welcome_window_open)
-                .default_width(400.0)
-                .scroll2([false, false])
-                .show(ui.ctx(), |ui| {
-                    ui.vertical_centered(|ui| {
-                        ui.add_space(20.0);
-                        ui.horizontal_wrapped(|ui| {
-                            ui.add_space(20.0);
-                            ui.label(LOREM_IPSUM);
-                        });
-                        ui.add_space(20.0);
-                    });
+            let response = Window::new(visual_order(&t.get("contract-explorer")))
+                .id(egui::Id::new("Contract Explorer"))
+                .open(contract_explorer_open)
+                .default_width(480.0)
+                .scroll2([false, true])
+                .show(ctx, |ui| {
+                    contract_explorer.ui(ui, network, storage);
+                });
+            record_window(regions, "Contract Explorer", response);
+
+            let response = Window::new(visual_order(&t.get("fonts")))
+                .id(egui::Id::new("Fonts"))
+                .open(fonts_window_open)
+                .default_width(360.0)
+                .scroll2([false, true])
+                .show(ctx, |ui| {
+                    fonts.ui(ui, &t, storage);
+                });
+            record_window(regions, "Fonts", response);
+
+            let response = Window::new(visual_order(&t.get("accessibility")))
+                .id(egui::Id::new("Accessibility"))
+                .open(accessibility_window_open)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    let before = accessibility.clone();
+                    if accessibility.ui(ui, &t) {
+                        // Keeps what was changed in the Style Settings window.
+                        *theme = Arc::new(before.theme_under(theme, &ctx.style()));
+                        accessibility.apply(ctx, theme);
+                    }
+                    if ui
+                        .button(visual_order(&t.get("contrast-report-ellipsis")))
+                        .clicked()
+                    {
+                        *contrast_window_open = true;
+                    }
+                });
+            record_window(regions, "Accessibility", response);
+
+            let response = Window::new(visual_order(&t.get("links")))
+                .id(egui::Id::new("Links"))
+                .open(links_window_open)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    links.ui(ui, &t);
+                });
+            record_window(regions, "Links", response);
+
+            let response = Window::new(visual_order(&t.get("log-console")))
+                .id(egui::Id::new("Log Console"))
+                .open(log_window_open)
+                .default_size([640.0, 320.0])
+                .show(ctx, |ui| {
+                    log_console.ui(ui, &t);
+                });
+            record_window(regions, "Log Console", response);
+
+            let response = Window::new(visual_order(&t.get("contrast-report")))
+                .id(egui::Id::new("Contrast Report"))
+                .open(contrast_window_open)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    crate::contrast::report_ui(ui, &t, &ctx.style().visuals);
                 });
-            Window::new("Window")
-                .anchor(Align2::RIGHT_TOP, vec2(-16.0, 16.0))
+            record_window(regions, "Contrast Report", response);
+
+            let response = Window::new(visual_order(&t.get("icons")))
+                .id(egui::Id::new("Icons"))
+                .open(icons_window_open)
+                .resizable(false)
+                .show(ctx, crate::icons::debug_ui);
+            record_window(regions, "Icons", response);
+
+            let response = Window::new(visual_order(&t.get("window")))
+                .id(egui::Id::new("Window"))
+                .anchor(
+                    direction.anchor(Align2::RIGHT_TOP),
+                    direction.offset(vec2(-16.0, 16.0)),
+                )
                 .open(connect_window_open)
-                .pivot(Align2::RIGHT_TOP)
+                .pivot(direction.anchor(Align2::RIGHT_TOP))
                 .default_width(240.0)
                 .show(ctx, |ui| {
                     ui.vertical_centered(|ui| {
@@ -325,14 +841,19 @@ impl eframe::App for TemplateApp {
                         ui.add_space(20.0);
                     })
                 });
-
-            Window::new("Alert")
-                .anchor(Align2::RIGHT_BOTTOM, vec2(-16.0, -16.0))
+            record_window(regions, "Window", response);
+
+            let response = Window::new(visual_order(&t.get("alert")))
+                .id(egui::Id::new("Alert"))
+                .anchor(
+                    direction.anchor(Align2::RIGHT_BOTTOM),
+                    direction.offset(vec2(-16.0, -16.0)),
+                )
                 .frame(Frame {
                     fill: Color32::from_rgb(41, 54, 31),
                     ..Frame::window(&ctx.style())
                 })
-                .pivot(Align2::RIGHT_BOTTOM)
+                .pivot(direction.anchor(Align2::RIGHT_BOTTOM))
                 .resizable(false)
                 .open(&mut self.alert_window_open)
                 .default_width(240.0)
@@ -343,68 +864,157 @@ impl eframe::App for TemplateApp {
                         ui.add_space(20.0);
                     })
                 });
+            record_window(regions, "Alert", response);
         });
-    }
-}
 
-pub const LOREM_IPSUM_SHORT: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
-pub const LOREM_IPSUM_MEDIUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat.";
-pub const LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
-
-//---------------------------------------------------------------
-// I needed to make a CustomHyperlink struct to open links in new tab by default.
-// The normal Hyperlink only opens in a new tab if a keyboard modifer is active.
-// ui.add(CustomHyperlink::from_label_and_url("new tab", "about:blank"));
+        let panels = [
+            ("Menu bar", "top_panel", cfg!(not(target_arch = "wasm32"))),
+            ("Menu bar", "top_bar", cfg!(target_arch = "wasm32")),
+            ("Bottom bar", "bottom_panel", true),
+            ("Left panel", "left_panel", *left_panel_open),
+            ("Right panel", "right_panel", *right_panel_open),
+        ];
+        for (name, id, shown) in panels {
+            if let Some(panel) = shown
+                .then(|| egui::panel::PanelState::load(ctx, egui::Id::new(id)))
+                .flatten()
+            {
+                regions.push((name, panel.rect));
+            }
+        }
+        if let Some(domain) = link_handler.confirm_ui(ctx, &t) {
+            links.policy.allowed.push(domain);
+        }
+        let response = help.ui(ctx, &t);
+        record_window(regions, "Help", response);
+        tour.ui(ctx, &t, regions);
+
+        let mut close_screenshot_window = false;
+        Window::new(visual_order(&t.get("save-screenshot")))
+            .id(egui::Id::new("Save Screenshot"))
+            .open(&mut self.screenshot_window_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if self.screenshot.ui(ui, &t, &self.regions) {
+                    // Closed before capturing, so it isn't in the picture.
+                    close_screenshot_window = true;
+                }
+            });
+        if close_screenshot_window {
+            self.screenshot_window_open = false;
+        }
+    }
 
-use egui::{Link, Response, Ui, Widget, WidgetText};
+    /// Paints the app again for a screenshot, see [`crate::screenshot::capture`]. Only
+    /// [`Self::show`] runs, and the regions recorded this frame are kept.
+    fn capture(
+        &mut self,
+        ctx: &egui::Context,
+        request: &crate::screenshot::Request,
+    ) -> Result<image::RgbaImage, String> {
+        let regions = std::mem::take(&mut self.regions);
+        let image = crate::screenshot::capture(ctx, request, |ctx| {
+            self.show(ctx);
+            std::mem::take(&mut self.regions)
+        });
+        self.regions = regions;
+        image
+    }
 
-#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
-pub struct CustomHyperlink {
-    url: String,
-    text: WidgetText,
-}
+    /// The panels and windows shown in the last frame.
+    #[cfg(test)]
+    pub(crate) fn regions(&self) -> &[Region] {
+        &self.regions
+    }
 
-#[allow(dead_code)]
-impl CustomHyperlink {
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn new(url: impl ToString) -> Self {
-        let url = url.to_string();
-        Self {
-            url: url.clone(),
-            text: url.into(),
+    fn save_screenshot(&mut self, ctx: &egui::Context, request: &crate::screenshot::Request) {
+        let t = Translator::load(ctx);
+        let saving = self.capture(ctx, request).and_then(|image| {
+            self.screenshot.save(ctx, &image).map_err(|err| {
+                let error = err.to_string();
+                t.format("screenshot-encode-failed", &[("error", (&error).into())])
+            })
+        });
+        if let Err(err) = saving {
+            self.notifications.push(Notification::error(err));
         }
     }
+}
 
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn from_label_and_url(text: impl Into<WidgetText>, url: impl ToString) -> Self {
-        Self {
-            url: url.to_string(),
-            text: text.into(),
+/// The items of the Help menu.
+fn help_menu(ui: &mut egui::Ui, t: &Translator, help: &mut Help, tour: &mut Tour) {
+    let pages = [
+        (Icon::Help, Page::Topics),
+        (Icon::Keyboard, Page::Shortcuts),
+        (Icon::Sparkle, Page::WhatsNew),
+        (Icon::Info, Page::About),
+    ];
+    for (icon, page) in pages {
+        if ui
+            .add(IconButton::new(icon).text(visual_order(&t.get(page.message_id()))))
+            .clicked()
+        {
+            help.open(page);
+            ui.close_menu();
         }
     }
+    ui.separator();
+    if ui
+        .add(IconButton::new(Icon::Flag).text(visual_order(&t.get("take-tour"))))
+        .clicked()
+    {
+        tour.start();
+        ui.close_menu();
+    }
 }
 
-impl Widget for CustomHyperlink {
-    fn ui(self, ui: &mut Ui) -> Response {
-        let Self { url, text } = self;
-
-        let response = ui.add(Link::new(text));
-        if response.clicked() {
-            ui.ctx().output_mut(|o| {
-                o.open_url = Some(egui::output::OpenUrl {
-                    url: url.clone(),
-                    new_tab: true,
-                });
-            });
+/// Lists the languages, each in itself, and the layout directions.
+fn language_menu(
+    ui: &mut egui::Ui,
+    t: &Translator,
+    language: &mut Language,
+    direction: &mut DirectionSetting,
+) {
+    ui.menu_button(visual_order(&t.get("menu-language")), |ui| {
+        for option in Language::ALL {
+            if ui
+                .selectable_label(*language == option, visual_order(option.native_name()))
+                .clicked()
+            {
+                *language = option;
+                Translator::new(option).install(ui.ctx());
+                ui.close_menu();
+            }
         }
-        if response.middle_clicked() {
-            ui.ctx().output_mut(|o| {
-                o.open_url = Some(egui::output::OpenUrl {
-                    url: url.clone(),
-                    new_tab: true,
-                });
-            });
+        ui.separator();
+        for option in DirectionSetting::ALL {
+            if ui
+                .radio(
+                    *direction == option,
+                    visual_order(&t.get(option.message_id())),
+                )
+                .clicked()
+            {
+                *direction = option;
+                ui.close_menu();
+            }
         }
-        response.on_hover_text(url)
+    });
+}
+
+/// Remembers where `title` was shown, for screenshots of a single window.
+fn record_window<R>(
+    regions: &mut Vec<Region>,
+    title: &'static str,
+    response: Option<egui::InnerResponse<R>>,
+) {
+    if let Some(response) = response {
+        regions.push((title, response.response.rect));
     }
 }
+
+#[cfg(test)]
+mod tests;
+
+pub const LOREM_IPSUM_SHORT: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+pub const LOREM_IPSUM_MEDIUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat.";
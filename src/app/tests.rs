@@ -0,0 +1,395 @@
+use egui::{Key, Modifiers};
+
+use crate::harness::Harness;
+
+/// A harness with the tour out of the way.
+fn harness() -> Harness {
+    let mut harness = Harness::new();
+    harness.key(Key::Escape, Modifiers::NONE);
+    harness.settle();
+    harness
+}
+
+#[test]
+fn right_panel_button_opens_windows() {
+    let mut harness = harness();
+    assert!(harness.find("Alert").is_none());
+
+    // The left and right panels both have a "Side Panel" heading; the button is only on the right.
+    harness.click_label("Button");
+    assert!(harness.app.connect_window_open);
+    assert!(harness.app.alert_window_open);
+
+    harness.settle();
+    let alert = harness.rect("Alert");
+    let window = harness.rect("Window");
+    let screen = harness.ctx.screen_rect();
+    // Both are anchored to the right, the alert at the bottom.
+    assert!(alert.right() > screen.center().x && window.right() > screen.center().x);
+    assert!(alert.top() > window.bottom());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn view_menu_toggles_panels() {
+    let mut harness = harness();
+    assert!(harness.find("Setting1: ").is_some());
+
+    harness.click_label("View");
+    harness.click_label("Left Side Panel");
+    assert!(!harness.app.left_panel_open);
+    harness.settle();
+    assert!(harness.find("Setting1: ").is_none());
+    assert_eq!(harness.find_all("Side Panel").len(), 1);
+
+    // The panel toggles leave the menu open.
+    harness.click_label("Right Side Panel");
+    assert!(!harness.app.right_panel_open);
+    harness.settle();
+    assert!(harness.find("Side Panel").is_none());
+
+    harness.click_label("Left Side Panel");
+    harness.settle();
+    assert!(harness.find("Setting1: ").is_some());
+}
+
+#[test]
+fn typing_into_a_setting() {
+    let mut harness = harness();
+    let label = harness.rect("Setting1: ");
+    // The text edit is right of its label.
+    harness.click(label.right_center() + egui::vec2(40.0, 0.0));
+
+    harness.key(Key::End, Modifiers::NONE);
+    harness.type_text(" edited");
+    assert_eq!(harness.app.setting1, "customizable value edited");
+
+    harness.key(Key::Backspace, Modifiers::NONE);
+    assert_eq!(harness.app.setting1, "customizable value edite");
+    assert!(harness.find("customizable value edite").is_some());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn zoom_shortcuts_change_the_ui_scale() {
+    let mut harness = harness();
+    harness.key(Key::PlusEquals, Modifiers::COMMAND);
+    harness.key(Key::PlusEquals, Modifiers::COMMAND);
+    assert_eq!(harness.app.accessibility.ui_scale, 1.2);
+    harness.key(Key::Minus, Modifiers::COMMAND);
+    assert_eq!(harness.app.accessibility.ui_scale, 1.1);
+    harness.key(Key::Num0, Modifiers::COMMAND);
+    assert_eq!(harness.app.accessibility.ui_scale, 1.0);
+}
+
+#[test]
+fn high_contrast_setting_applies_right_away() {
+    let mut harness = harness();
+    harness.app.accessibility_window_open = true;
+    harness.settle();
+    let panel_fill = harness.ctx.style().visuals.panel_fill;
+
+    harness.click_label("High contrast");
+    assert!(harness.app.accessibility.high_contrast);
+    assert_ne!(harness.ctx.style().visuals.panel_fill, panel_fill);
+}
+
+#[test]
+fn contrast_report_of_the_theme() {
+    let mut harness = harness();
+    harness.app.accessibility_window_open = true;
+    harness.settle();
+    harness.click_label("Contrast Report…");
+    harness.settle();
+    assert!(harness
+        .find("All text in this theme passes WCAG AA.")
+        .is_some());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn language_menu_translates_the_ui() {
+    let mut harness = harness();
+    harness.click_label("View");
+    harness.click_label("Language");
+    // Hebrew reads right to left, also in an English menu.
+    assert!(harness.find("תירבע").is_some());
+    harness.click_label("Deutsch");
+    harness.settle();
+    assert_eq!(harness.app.language, crate::i18n::Language::German);
+    assert!(harness.find("Datei").is_some());
+    assert_eq!(harness.find_all("Seitenleiste").len(), 2);
+    assert!(harness.find("File").is_none());
+}
+
+#[test]
+fn right_to_left_layout_mirrors_panels_and_windows() {
+    let mut harness = harness();
+    harness.app.layout_direction = crate::direction::DirectionSetting::RightToLeft;
+    harness.settle();
+    let screen = harness.ctx.screen_rect();
+    // The settings panel is on the right now, and the one with the button on the left.
+    assert!(harness.rect("Setting1: ").left() > screen.center().x);
+    assert!(harness.rect("Button").right() < screen.center().x);
+
+    harness.click_label("Button");
+    harness.settle();
+    let alert = harness.rect("Alert");
+    let window = harness.rect("Window");
+    assert!(alert.left() < screen.center().x && window.left() < screen.center().x);
+    assert!(alert.top() > window.bottom());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn tour_steps_through_the_ui() {
+    let mut harness = Harness::new();
+    harness.settle();
+    assert!(harness.find("Welcome").is_some());
+    harness.click_label("Next");
+    harness.settle();
+    assert!(harness.find("Menu bar").is_some());
+    harness.click_label("Back");
+    harness.settle();
+    assert!(harness.find("Welcome").is_some());
+
+    harness.click_label("Don't show again");
+    harness.click_label("Skip");
+    harness.settle();
+    assert!(harness.find("Welcome").is_none());
+    assert!(harness.app.tour.dont_show_again);
+
+    // It can still be taken from the Help menu.
+    harness.click_label("Help");
+    harness.click_label("Take the Tour");
+    harness.settle();
+    assert!(harness.find("Welcome").is_some());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn tour_takes_the_clicks_outside_the_spotlight() {
+    let mut harness = Harness::new();
+    harness.settle();
+    harness.click_label("Next");
+    harness.settle();
+    assert!(harness.find("Menu bar").is_some());
+    harness.click_label("Button");
+    assert!(!harness.app.connect_window_open);
+
+    // The right panel, with the button, is in the spotlight two steps on.
+    harness.click_label("Next");
+    harness.settle();
+    harness.click_label("Next");
+    harness.settle();
+    harness.click_label("Button");
+    assert!(harness.app.connect_window_open);
+}
+
+#[test]
+fn help_topics_are_searchable() {
+    let mut harness = harness();
+    harness.key(Key::F1, Modifiers::NONE);
+    harness.settle();
+    assert!(harness.find("Contract explorer").is_some());
+
+    harness.click_label("Search");
+    harness.type_text("wcag");
+    harness.settle();
+    assert!(harness.find("Contract explorer").is_none());
+    // In the list and as the heading of the page.
+    assert_eq!(harness.find_all("Appearance and accessibility").len(), 2);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn help_menu_opens_the_about_page() {
+    let mut harness = harness();
+    harness.click_label("Help");
+    harness.click_label("About");
+    harness.settle();
+    assert!(harness.find(crate::help::VERSION).is_some());
+}
+
+#[test]
+fn whats_new_opens_once_after_an_update() {
+    let mut harness = harness();
+    // Not on the first start.
+    assert!(harness.find("Added").is_none());
+
+    harness.app.help = serde_json::from_str(r#"{ "last_version": "0.0.1" }"#).unwrap();
+    harness.settle();
+    assert!(harness.find("Added").is_some());
+    let saved = serde_json::to_value(&harness.app.help).unwrap();
+    assert_eq!(saved["last_version"], crate::help::VERSION);
+}
+
+#[test]
+fn unknown_links_ask_before_opening() {
+    let mut harness = harness();
+    let url = "https://example.com/repo";
+    harness.app.links.github = url.to_owned();
+    harness.run();
+
+    harness.click_label("GitHub");
+    harness.settle();
+    assert!(harness.opened_urls.is_empty());
+    assert!(harness.find("Open this link?").is_some());
+
+    harness.click_label("Always open links to example.com");
+    harness.click_label("Open");
+    assert_eq!(harness.opened_urls, [url]);
+    assert!(harness
+        .app
+        .links
+        .policy
+        .allowed
+        .contains(&"example.com".to_owned()));
+
+    // Trusted now.
+    harness.settle();
+    harness.click_label("GitHub");
+    assert_eq!(harness.opened_urls, [url, url]);
+    assert!(harness.find("Open this link?").is_none());
+}
+
+#[test]
+fn dangerous_links_are_blocked() {
+    let mut harness = harness();
+    harness.app.links.github = "javascript:alert(1)".to_owned();
+    harness.run();
+
+    harness.click_label("GitHub");
+    harness.settle();
+    assert!(harness.opened_urls.is_empty());
+    assert!(harness.find("Open this link?").is_none());
+    assert!(harness
+        .find("Blocked a link to javascript:alert(1)")
+        .is_some());
+}
+
+#[test]
+fn log_console_filters_events() {
+    use tracing_subscriber::prelude::*;
+
+    let logs = crate::logs::Logs::default();
+    let subscriber = tracing_subscriber::registry().with(logs.layer());
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "app::network", "connected to pulsar-3");
+        tracing::warn!(target: "app::storage", "storage is almost full");
+    });
+    let mut harness = harness();
+    harness.app.log_console = crate::logs::LogConsole::new(logs);
+    harness.app.log_window_open = true;
+    harness.settle();
+    assert!(harness.find("connected to pulsar-3").is_some());
+    assert!(harness.find("2 of 2 messages").is_some());
+
+    harness.click_label("Search");
+    harness.type_text("STORAGE");
+    harness.settle();
+    assert!(harness.find("connected to pulsar-3").is_none());
+    assert!(harness.find("storage is almost full").is_some());
+    assert!(harness.find("1 of 2 messages").is_some());
+}
+
+// Snapshots of the custom theme: roundings, expansions and shadows of windows, menus and
+// widgets. See `crate::harness` for how to update them. The snapshots are of debug builds,
+// which show a warning in the bottom bar, so release builds skip them.
+
+#[test]
+#[cfg_attr(not(debug_assertions), ignore = "snapshots are of debug builds")]
+fn snapshot_default() {
+    let mut harness = Harness::new();
+    harness.settle();
+    harness.assert_snapshot("default");
+}
+
+#[test]
+#[cfg_attr(not(debug_assertions), ignore = "snapshots are of debug builds")]
+fn snapshot_windows() {
+    let mut harness = harness();
+    harness.click_label("Button");
+    // Hover the button, so its hovered style is in the picture too.
+    harness.hover(harness.rect("Button").center());
+    harness.settle();
+    harness.assert_snapshot("windows");
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+#[cfg_attr(not(debug_assertions), ignore = "snapshots are of debug builds")]
+fn snapshot_menu() {
+    let mut harness = harness();
+    harness.click_label("View");
+    harness.hover(harness.rect("Style Settings").center());
+    harness.settle();
+    harness.assert_snapshot("menu");
+}
+
+#[test]
+fn screenshot_of_a_region() {
+    let mut harness = harness();
+    let panel = harness
+        .app
+        .regions
+        .iter()
+        .find(|(name, _)| *name == "Right panel")
+        .unwrap()
+        .1;
+
+    let request = crate::screenshot::Request {
+        scale: 2.0,
+        region: Some("Right panel"),
+    };
+    let image = harness.app.capture(&harness.ctx, &request).unwrap();
+    assert_eq!(image.width(), (panel.width() * 2.0).round() as u32);
+    assert_eq!(image.height(), (panel.height() * 2.0).round() as u32);
+    // Painted, not transparent.
+    assert!(image.pixels().all(|pixel| pixel[3] == 255));
+}
+
+#[test]
+fn screenshots_leave_the_frame_alone() {
+    let mut harness = harness();
+    let regions = harness.app.regions.clone();
+    harness.app.router.request(crate::router::Route::Settings);
+    let blocked = egui::output::OpenUrl::new_tab("javascript:alert(1)");
+    crate::links::Links::load(&harness.ctx).open(&harness.ctx, blocked);
+
+    let request = crate::screenshot::Request {
+        scale: 1.0,
+        region: None,
+    };
+    harness.app.capture(&harness.ctx, &request).unwrap();
+    assert_eq!(harness.app.regions, regions);
+
+    // The route and the blocked link are still there for the next frame.
+    harness.settle();
+    assert!(harness.app.style_window_open);
+    assert!(harness
+        .find("Blocked a link to javascript:alert(1)")
+        .is_some());
+}
+
+#[test]
+fn regions_have_translated_names() {
+    let mut harness = harness();
+    let app = &mut harness.app;
+    (app.connect_window_open, app.alert_window_open) = (true, true);
+    // Not the contract explorer, which would fetch from the network.
+    (app.style_window_open, app.fonts_window_open) = (true, true);
+    app.accessibility_window_open = true;
+    (app.contrast_window_open, app.icons_window_open) = (true, true);
+    (app.links_window_open, app.log_window_open) = (true, true);
+    app.help.open(crate::help::Page::Topics);
+    harness.settle();
+
+    for (name, _) in &harness.app.regions {
+        assert!(
+            crate::screenshot::region_message_id(name).is_some(),
+            "{name} has no message"
+        );
+    }
+    assert!(crate::screenshot::region_message_id("Contract Explorer").is_some());
+}
@@ -0,0 +1,371 @@
+//! Images by id: SVG, PNG, JPEG and WebP, bundled with the app or registered at runtime.
+//!
+//! Images are decoded the first time they are shown. SVGs are rasterized at the size they
+//! are shown at, in physical pixels, and again when `pixels_per_point` changes, so they stay
+//! sharp. Monochrome icons can be recolored, e.g. to the text color of the theme.
+//!
+//! ```ignore
+//! let assets = Assets::load(ui.ctx());
+//! assets.image(ui, "scrt", 64.0, Some(ui.visuals().text_color()));
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+use egui::epaint::{mutex::RwLock, textures::TextureManager};
+use egui::{Color32, ColorImage, Response, Sense, Stroke, TextureHandle, TextureOptions, Ui};
+
+use crate::i18n::Translator;
+use crate::icons::Icon;
+
+/// The images compiled into the app, by id, besides the [`Icon`]s.
+const BUNDLED: &[(&str, &[u8])] = &[("scrt", include_bytes!("../assets/scrt.svg"))];
+
+/// Textures not shown for this many seconds are freed.
+const UNUSED_TIMEOUT: f64 = 10.0;
+
+/// One rasterization of an image.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    id: String,
+    /// Height in pixels, for SVGs. Other images have one size.
+    height: Option<u32>,
+    tint: Option<Color32>,
+}
+
+/// Why an image couldn't be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetError {
+    /// No image was registered with the id.
+    Unknown,
+    /// The bytes aren't an image egui can decode.
+    Decode(String),
+}
+
+impl AssetError {
+    /// What to tell the user about image `id`.
+    pub fn message(&self, t: &Translator, id: &str) -> String {
+        match self {
+            AssetError::Unknown => t.format("asset-unknown", &[("id", id.into())]),
+            AssetError::Decode(err) => t.format(
+                "asset-load-failed",
+                &[("id", id.into()), ("error", err.into())],
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for AssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetError::Unknown => write!(f, "there is no image with this id"),
+            AssetError::Decode(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+struct Entry {
+    texture: TextureHandle,
+    last_used: f64,
+}
+
+struct Inner {
+    sources: HashMap<String, Cow<'static, [u8]>>,
+    textures: HashMap<Key, Entry>,
+    /// Where the textures are. A context with a copy of the memory, like a screenshot's,
+    /// shares the `Assets` but has textures of its own.
+    texture_manager: Weak<RwLock<TextureManager>>,
+    /// Images that failed to load aren't tried again until they are registered again.
+    failed: HashMap<String, AssetError>,
+    errors: Vec<(String, AssetError)>,
+    pixels_per_point: f32,
+    last_sweep: f64,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        Self {
+            sources: BUNDLED
+                .iter()
+                .map(|(id, bytes)| (id.to_string(), *bytes))
+                .chain(Icon::ALL.iter().map(|icon| (icon.asset_id(), icon.svg())))
+                .map(|(id, bytes)| (id, Cow::Borrowed(bytes)))
+                .collect(),
+            textures: HashMap::new(),
+            texture_manager: Weak::new(),
+            failed: HashMap::new(),
+            errors: Vec::new(),
+            pixels_per_point: 1.0,
+            last_sweep: 0.0,
+        }
+    }
+}
+
+/// The images of a context and their textures. Cheap to clone; clones share the cache.
+#[derive(Clone, Default)]
+pub struct Assets {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Assets {
+    /// The assets of `ctx`, starting with the bundled ones.
+    pub fn load(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_insert_with(assets_id(), Self::default)
+                .clone()
+        })
+    }
+
+    /// Adds or replaces the image `id`, e.g. one that was downloaded.
+    pub fn register(&self, id: impl Into<String>, bytes: Vec<u8>) {
+        let id = id.into();
+        let mut inner = self.inner.lock().unwrap();
+        inner.textures.retain(|key, _| key.id != id);
+        inner.failed.remove(&id);
+        inner.sources.insert(id, Cow::Owned(bytes));
+    }
+
+    /// The texture of image `id`, `height` points high if it is an SVG, and with every
+    /// pixel in the `tint` color if given, keeping only its alpha.
+    pub fn texture(
+        &self,
+        ctx: &egui::Context,
+        id: &str,
+        height: f32,
+        tint: Option<Color32>,
+    ) -> Result<TextureHandle, AssetError> {
+        let pixels_per_point = ctx.pixels_per_point();
+        let now = ctx.input(|i| i.time);
+        let texture_manager = ctx.tex_manager();
+        let mut inner = self.inner.lock().unwrap();
+        if !std::ptr::eq(
+            inner.texture_manager.as_ptr(),
+            Arc::as_ptr(&texture_manager),
+        ) {
+            inner.texture_manager = Arc::downgrade(&texture_manager);
+            inner.textures.clear();
+        }
+        if inner.pixels_per_point != pixels_per_point {
+            inner.pixels_per_point = pixels_per_point;
+            inner.textures.retain(|key, _| key.height.is_none());
+        }
+        if now - inner.last_sweep > 1.0 {
+            inner.last_sweep = now;
+            inner
+                .textures
+                .retain(|_, entry| now - entry.last_used < UNUSED_TIMEOUT);
+        }
+        if let Some(err) = inner.failed.get(id) {
+            return Err(err.clone());
+        }
+        let Some(bytes) = inner.sources.get(id) else {
+            return Err(inner.fail(ctx, id, AssetError::Unknown));
+        };
+
+        let svg = is_svg(bytes);
+        let key = Key {
+            id: id.to_owned(),
+            height: svg.then(|| (height * pixels_per_point).round().max(1.0) as u32),
+            tint,
+        };
+        if let Some(entry) = inner.textures.get_mut(&key) {
+            entry.last_used = now;
+            return Ok(entry.texture.clone());
+        }
+
+        let bytes = inner.sources.get(id).unwrap();
+        let decoded = match key.height {
+            Some(height) => egui_extras::image::load_svg_bytes_with_size(
+                bytes,
+                egui_extras::image::FitTo::Height(height),
+            ),
+            None => egui_extras::image::load_image_bytes(bytes),
+        };
+        let mut image = decoded.map_err(|err| inner.fail(ctx, id, AssetError::Decode(err)))?;
+        if let Some(tint) = tint {
+            recolor(&mut image, tint);
+        }
+        let texture = ctx.load_texture(format!("asset {id}"), image, TextureOptions::LINEAR);
+        inner.textures.insert(
+            key,
+            Entry {
+                texture: texture.clone(),
+                last_used: now,
+            },
+        );
+        Ok(texture)
+    }
+
+    /// Shows image `id`, `height` points high. Shows a crossed-out box if it can't be loaded.
+    pub fn image(&self, ui: &mut Ui, id: &str, height: f32, tint: Option<Color32>) -> Response {
+        match self.texture(ui.ctx(), id, height, tint) {
+            Ok(texture) => {
+                let size = texture.size_vec2();
+                let width = size.x / size.y * height;
+                ui.image(&texture, egui::vec2(width, height))
+            }
+            Err(err) => {
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(height, height), Sense::hover());
+                let stroke = Stroke::new(1.0, ui.visuals().error_fg_color);
+                let painter = ui.painter();
+                painter.rect_stroke(rect, 0.0, stroke);
+                painter.line_segment([rect.left_top(), rect.right_bottom()], stroke);
+                painter.line_segment([rect.right_top(), rect.left_bottom()], stroke);
+                response.on_hover_text(err.message(&Translator::load(ui.ctx()), id))
+            }
+        }
+    }
+
+    /// Load errors since the last call, with the ids of their images, to show to the user.
+    pub fn take_errors(&self) -> Vec<(String, AssetError)> {
+        std::mem::take(&mut self.inner.lock().unwrap().errors)
+    }
+}
+
+impl Inner {
+    /// Remembers that image `id` failed to load and reports it, in the next frame.
+    fn fail(&mut self, ctx: &egui::Context, id: &str, err: AssetError) -> AssetError {
+        tracing::warn!("failed to load image {id:?}: {err}");
+        ctx.request_repaint();
+        self.failed.insert(id.to_owned(), err.clone());
+        self.errors.push((id.to_owned(), err.clone()));
+        err
+    }
+}
+
+fn assets_id() -> egui::Id {
+    egui::Id::new("assets")
+}
+
+/// PNG, JPEG and WebP are recognized by their first bytes; anything else is tried as SVG.
+fn is_svg(bytes: &[u8]) -> bool {
+    image::guess_format(bytes).is_err()
+}
+
+/// Paints every pixel in `color`, keeping its alpha.
+fn recolor(image: &mut ColorImage, color: Color32) {
+    for pixel in &mut image.pixels {
+        *pixel = color.linear_multiply(pixel.a() as f32 / 255.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+
+    /// Runs `f` in a frame with `pixels_per_point`.
+    fn frame(ctx: &egui::Context, pixels_per_point: f32, f: impl FnOnce(&egui::Context)) {
+        let input = egui::RawInput {
+            pixels_per_point: Some(pixels_per_point),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, f);
+    }
+
+    #[test]
+    fn svgs_are_rasterized_at_the_scale() {
+        let ctx = egui::Context::default();
+        let assets = Assets::load(&ctx);
+        frame(&ctx, 1.0, |ctx| {
+            let texture = assets.texture(ctx, "scrt", 32.0, None).unwrap();
+            assert_eq!(texture.size(), [32, 32]);
+            let again = assets.texture(ctx, "scrt", 32.0, None).unwrap();
+            assert_eq!(again.id(), texture.id());
+        });
+        frame(&ctx, 2.0, |ctx| {
+            let texture = assets.texture(ctx, "scrt", 32.0, None).unwrap();
+            assert_eq!(texture.size(), [64, 64]);
+        });
+    }
+
+    #[test]
+    fn icons_are_recolored() {
+        let ctx = egui::Context::default();
+        let assets = Assets::load(&ctx);
+        let mut image = ColorImage::new([2, 1], Color32::TRANSPARENT);
+        image.pixels[1] = Color32::from_rgb(10, 20, 30);
+        recolor(&mut image, Color32::RED);
+        assert_eq!(image.pixels, [Color32::TRANSPARENT, Color32::RED]);
+
+        frame(&ctx, 1.0, |ctx| {
+            let plain = assets.texture(ctx, "scrt", 16.0, None).unwrap();
+            let red = assets
+                .texture(ctx, "scrt", 16.0, Some(Color32::RED))
+                .unwrap();
+            assert_ne!(plain.id(), red.id());
+        });
+    }
+
+    #[test]
+    fn load_errors_are_reported() {
+        let ctx = egui::Context::default();
+        let assets = Assets::load(&ctx);
+        assets.register("broken", b"not an image".to_vec());
+        let png = {
+            let mut bytes = Vec::new();
+            let image = image::RgbaImage::new(3, 2);
+            image
+                .write_to(
+                    &mut std::io::Cursor::new(&mut bytes),
+                    image::ImageOutputFormat::Png,
+                )
+                .unwrap();
+            bytes
+        };
+        assets.register("png", png);
+
+        frame(&ctx, 1.0, |ctx| {
+            assert!(assets.texture(ctx, "broken", 16.0, None).is_err());
+            assert!(assets.texture(ctx, "broken", 16.0, None).is_err());
+            assert!(assets.texture(ctx, "missing", 16.0, None).is_err());
+            // Only SVGs are resized.
+            let texture = assets.texture(ctx, "png", 16.0, None).unwrap();
+            assert_eq!(texture.size(), [3, 2]);
+        });
+        // Reported once, not on every frame.
+        let errors = assets.take_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, "broken");
+        assert!(matches!(errors[0].1, AssetError::Decode(_)));
+        assert_eq!(errors[1], ("missing".to_owned(), AssetError::Unknown));
+        assert!(assets.take_errors().is_empty());
+
+        let t = Translator::new(Language::English);
+        assert_eq!(
+            errors[1].1.message(&t, "missing"),
+            "There is no image missing"
+        );
+    }
+
+    #[test]
+    fn screenshots_have_the_icons() {
+        let ctx = egui::Context::default();
+        let show = |ctx: &egui::Context| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let icon = Icon::Camera.asset_id();
+                Assets::load(ctx).image(ui, &icon, 32.0, Some(Color32::RED));
+            });
+            Vec::new()
+        };
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                Default::default(),
+                egui::vec2(64.0, 64.0),
+            )),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            show(ctx);
+        });
+
+        let request = crate::screenshot::Request {
+            scale: 1.0,
+            region: None,
+        };
+        let image = crate::screenshot::capture(&ctx, &request, show).unwrap();
+        assert!(image.pixels().any(|pixel| pixel[0] > 200 && pixel[1] < 50));
+    }
+}
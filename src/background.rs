@@ -0,0 +1,146 @@
+//! What is painted behind the contents of the central panel: a branded image, a gradient,
+//! or a slowly drifting pattern. The theme picks one, see [`crate::style::my_background`].
+//!
+//! The pattern only moves while the window has focus and reduced motion is off, and then
+//! in steps of a quarter point, so it doesn't keep the CPU busy.
+
+use std::time::Duration;
+
+use egui::{pos2, Color32, Mesh, Rect, Shape, Ui};
+
+use crate::assets::Assets;
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Background {
+    None,
+    /// An image from [`Assets`], in the center.
+    Image {
+        asset: String,
+        height: f32,
+        /// From 0 (invisible) to 1.
+        opacity: f32,
+    },
+    /// From `top` to `bottom`.
+    Gradient {
+        top: Color32,
+        bottom: Color32,
+    },
+    /// A grid of dots, drifting down and to the right at `speed` points per second.
+    Pattern {
+        color: Color32,
+        spacing: f32,
+        radius: f32,
+        speed: f32,
+    },
+}
+
+impl Background {
+    /// Paints the background into `rect`, before anything else in `ui`.
+    pub fn paint(&self, ui: &Ui, rect: Rect) {
+        let painter = ui.painter_at(rect);
+        match self {
+            Background::None => {}
+            Background::Image {
+                asset,
+                height,
+                opacity,
+            } => {
+                // Errors are reported by the assets.
+                let assets = Assets::load(ui.ctx());
+                if let Ok(texture) = assets.texture(ui.ctx(), asset, *height, None) {
+                    let size = texture.size_vec2();
+                    let size = size * (*height / size.y);
+                    let image = Rect::from_center_size(rect.center(), size);
+                    let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+                    let tint = Color32::WHITE.linear_multiply(*opacity);
+                    painter.image(texture.id(), image, uv, tint);
+                }
+            }
+            Background::Gradient { top, bottom } => {
+                let mut mesh = Mesh::default();
+                mesh.colored_vertex(rect.left_top(), *top);
+                mesh.colored_vertex(rect.right_top(), *top);
+                mesh.colored_vertex(rect.left_bottom(), *bottom);
+                mesh.colored_vertex(rect.right_bottom(), *bottom);
+                mesh.add_triangle(0, 1, 2);
+                mesh.add_triangle(1, 2, 3);
+                painter.add(Shape::mesh(mesh));
+            }
+            Background::Pattern {
+                color,
+                spacing,
+                radius,
+                speed,
+            } => {
+                let spacing = spacing.max(4.0);
+                let offset = if animates(ui.ctx()) && *speed > 0.0 {
+                    let steps = 4.0 * speed; // per second
+                    ui.ctx()
+                        .request_repaint_after(Duration::from_secs_f32(1.0 / steps));
+                    let time = ui.input(|i| i.time);
+                    (time as f32 * speed) % spacing
+                } else {
+                    0.0
+                };
+                // Anchored to the screen, so the dots stay put when the panel is resized.
+                let first = |min: f32| (min / spacing).floor() * spacing + offset - spacing;
+                let mut y = first(rect.top());
+                while y < rect.bottom() + spacing {
+                    let mut x = first(rect.left());
+                    while x < rect.right() + spacing {
+                        painter.circle_filled(pos2(x, y), *radius, *color);
+                        x += spacing;
+                    }
+                    y += spacing;
+                }
+            }
+        }
+    }
+}
+
+/// Whether moving backgrounds should move now.
+fn animates(ctx: &egui::Context) -> bool {
+    ctx.input(|i| i.raw.has_focus) && !crate::accessibility::reduced_motion(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The output of painting `background`, after the first frame, which always repaints.
+    fn paint(background: &Background, has_focus: bool, reduced_motion: bool) -> egui::FullOutput {
+        let ctx = egui::Context::default();
+        let accessibility = crate::accessibility::Accessibility {
+            reduced_motion,
+            ..Default::default()
+        };
+        accessibility.apply(&ctx, &Default::default());
+        let input = || egui::RawInput {
+            has_focus,
+            ..Default::default()
+        };
+        let run = || {
+            ctx.run(input(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    background.paint(ui, ui.max_rect());
+                });
+            })
+        };
+        run();
+        run()
+    }
+
+    #[test]
+    fn pattern_pauses_without_focus_or_motion() {
+        let pattern = Background::Pattern {
+            color: Color32::WHITE,
+            spacing: 20.0,
+            radius: 1.0,
+            speed: 4.0,
+        };
+        let animated = paint(&pattern, true, false);
+        assert_eq!(animated.repaint_after, Duration::from_secs_f32(1.0 / 16.0));
+        assert_eq!(paint(&pattern, false, false).repaint_after, Duration::MAX);
+        assert_eq!(paint(&pattern, true, true).repaint_after, Duration::MAX);
+    }
+}
@@ -0,0 +1,176 @@
+//! Command line arguments of the native app.
+
+use std::path::PathBuf;
+
+use eframe::egui;
+use eframe_template::{network::Network, router::Route, AppOptions};
+use tracing_subscriber::filter::LevelFilter;
+
+pub const HELP: &str = "\
+Usage: eframe_template [OPTIONS] [ROUTE]
+
+Arguments:
+  [ROUTE]                 What to show first, e.g. `/contracts` or `/contract/secret1...`
+                          (the same paths as the web build's URL hash)
+
+Options:
+  --theme <FILE>          RON file with an egui::Style to use instead of the built-in one
+                          (fields that are left out get egui's defaults)
+  --print-theme           Print the built-in style, as a starting point for --theme
+  --network <NAME>        mainnet, testnet or local, or a chain id like pulsar-3
+  --storage-dir <DIR>     Keep the app state and data in DIR, e.g. for portable installs or
+                          separate profiles
+  --size <WxH>            Initial window size in points [default: 640x480]
+  --position <X,Y>        Initial window position in points [default: centered]
+  --reset                 Start with the default state instead of the saved one
+  --log-level <LEVEL>     off, error, warn, info, debug or trace [default: info]
+  -h, --help              Print this help
+";
+
+pub struct Cli {
+    pub help: bool,
+    pub print_theme: bool,
+    pub log_level: Option<LevelFilter>,
+    pub window_size: Option<egui::Vec2>,
+    pub window_position: Option<egui::Pos2>,
+    pub theme: Option<PathBuf>,
+    pub app: AppOptions,
+}
+
+impl Cli {
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(pico_args::Arguments::from_env())
+    }
+
+    fn parse(mut args: pico_args::Arguments) -> Result<Self, String> {
+        let err = |err: pico_args::Error| err.to_string();
+        let mut cli = Self {
+            help: args.contains(["-h", "--help"]),
+            print_theme: args.contains("--print-theme"),
+            log_level: args.opt_value_from_str("--log-level").map_err(err)?,
+            window_size: args.opt_value_from_fn("--size", parse_size).map_err(err)?,
+            window_position: args
+                .opt_value_from_fn("--position", parse_position)
+                .map_err(err)?,
+            theme: args.opt_value_from_str("--theme").map_err(err)?,
+            app: AppOptions {
+                network: args
+                    .opt_value_from_fn("--network", |name| {
+                        Network::from_name(name).ok_or_else(|| format!("unknown network: {name}"))
+                    })
+                    .map_err(err)?,
+                storage_dir: args.opt_value_from_str("--storage-dir").map_err(err)?,
+                reset_state: args.contains("--reset"),
+                ..Default::default()
+            },
+        };
+        cli.app.route = args.opt_free_from_fn(str::parse::<Route>).map_err(err)?;
+
+        let rest = args.finish();
+        if !rest.is_empty() {
+            return Err(format!("unexpected arguments: {rest:?}"));
+        }
+        Ok(cli)
+    }
+
+    /// Reads the `--theme` file into `app.style`.
+    pub fn load_theme(&mut self) -> Result<(), String> {
+        let Some(path) = &self.theme else {
+            return Ok(());
+        };
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let style = ron::from_str(&text).map_err(|err| format!("{}: {err}", path.display()))?;
+        self.app.style = Some(style);
+        Ok(())
+    }
+}
+
+pub fn built_in_theme() -> String {
+    ron::ser::to_string_pretty(
+        &eframe_template::style::my_style(),
+        ron::ser::PrettyConfig::default(),
+    )
+    .unwrap()
+}
+
+/// `800x600`
+fn parse_size(size: &str) -> Result<egui::Vec2, String> {
+    let (w, h) = size
+        .split_once('x')
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got {size}"))?;
+    let size = egui::vec2(parse_number(w)?, parse_number(h)?);
+    if size.x > 0.0 && size.y > 0.0 {
+        Ok(size)
+    } else {
+        Err(format!("the window size must be positive, got {w}x{h}"))
+    }
+}
+
+/// `100,200`
+fn parse_position(position: &str) -> Result<egui::Pos2, String> {
+    let (x, y) = position
+        .split_once(',')
+        .ok_or_else(|| format!("expected X,Y, got {position}"))?;
+    Ok(egui::pos2(parse_number(x)?, parse_number(y)?))
+}
+
+/// A finite number: `inf` and `NaN` parse as `f32` too.
+fn parse_number(number: &str) -> Result<f32, String> {
+    number
+        .trim()
+        .parse()
+        .ok()
+        .filter(|number: &f32| number.is_finite())
+        .ok_or_else(|| format!("not a number: {number}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, String> {
+        Cli::parse(pico_args::Arguments::from_vec(
+            args.iter().map(Into::into).collect(),
+        ))
+    }
+
+    #[test]
+    fn parses_options() {
+        let cli = parse(&[
+            "--size",
+            "800x600",
+            "--network",
+            "pulsar-3",
+            "--reset",
+            "/contracts",
+        ])
+        .unwrap();
+        assert_eq!(cli.window_size, Some(egui::vec2(800.0, 600.0)));
+        assert_eq!(cli.app.network, Some(Network::Testnet));
+        assert_eq!(cli.app.route, Some(Route::Contracts));
+        assert!(cli.app.reset_state);
+
+        assert!(parse(&["--network", "moon"]).is_err());
+        assert!(parse(&["--size", "800"]).is_err());
+        for size in ["0x600", "800x-600", "NaNx600", "800xinf"] {
+            assert!(parse(&["--size", size]).is_err(), "{size}");
+        }
+        // Windows may start left of or above the primary screen.
+        let cli = parse(&["--position", "-100,20.5"]).unwrap();
+        assert_eq!(cli.window_position, Some(egui::pos2(-100.0, 20.5)));
+        for position in ["NaN,0", "0,-inf", "1e39,0"] {
+            assert!(parse(&["--position", position]).is_err(), "{position}");
+        }
+        assert!(parse(&["/contracts", "extra"]).is_err());
+        assert!(parse(&["/contract/.."]).is_err());
+    }
+
+    #[test]
+    fn built_in_theme_round_trips() {
+        let style: egui::Style = ron::from_str(&built_in_theme()).unwrap();
+        let built_in = eframe_template::style::my_style();
+        assert_eq!(style.visuals.panel_fill, built_in.visuals.panel_fill);
+        assert_eq!(style.text_styles, built_in.text_styles);
+    }
+}
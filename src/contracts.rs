@@ -0,0 +1,753 @@
+//! Contract explorer: browse uploaded code and instantiated contracts on a network,
+//! and build query/execute messages from a contract's JSON schema.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use egui::{CollapsingHeader, ComboBox, RichText, ScrollArea, TextEdit, Ui};
+use serde_json::{Map, Value};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+use crate::json_tree::JsonTree;
+use crate::network::Network;
+use crate::storage::Storage;
+use crate::style::{change_animation_time, reset_animation_time};
+use crate::text_styles::{AppTextStyle, RichTextExt};
+
+/// The result of a request that may still be in flight.
+type Pending<T> = Arc<Mutex<Option<Result<T, String>>>>;
+
+/// Fires off a GET request and parses the body as JSON once it arrives.
+///
+/// The response is saved under `cache_key`, and the saved copy is used if the request fails.
+fn fetch_json<T>(
+    ctx: &egui::Context,
+    url: String,
+    storage: &Storage,
+    cache_key: String,
+) -> Pending<T>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    fn parse<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+        serde_json::from_slice(bytes).map_err(|err| err.to_string())
+    }
+
+    let pending = Pending::default();
+    let slot = pending.clone();
+    let ctx = ctx.clone();
+    let storage = storage.clone();
+    crate::http::get(url, move |result| match result {
+        Ok(bytes) => {
+            let parsed = parse(&bytes);
+            if parsed.is_ok() {
+                storage.set(&cache_key, bytes);
+            }
+            *slot.lock().unwrap() = Some(parsed);
+            ctx.request_repaint();
+        }
+        Err(err) => storage.get(&cache_key.clone(), move |cached| {
+            let parsed = match cached {
+                Some(bytes) => {
+                    tracing::info!("request failed ({err}), using the saved {cache_key}");
+                    parse(&bytes)
+                }
+                None => Err(err),
+            };
+            *slot.lock().unwrap() = Some(parsed);
+            ctx.request_repaint();
+        }),
+    });
+    pending
+}
+
+/// Shows a spinner or the error while `pending` is unresolved, otherwise calls `add_contents`.
+fn show_pending<T>(ui: &mut Ui, pending: &Pending<T>, add_contents: impl FnOnce(&mut Ui, &T)) {
+    match &*pending.lock().unwrap() {
+        None => {
+            ui.spinner();
+        }
+        Some(Err(err)) => {
+            ui.add(BidiLabel::new(
+                RichText::new(err).color(ui.visuals().error_fg_color),
+            ));
+        }
+        Some(Ok(value)) => add_contents(ui, value),
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+struct CodesResponse {
+    code_infos: Vec<CodeInfo>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+struct CodeInfo {
+    code_id: String,
+    creator: String,
+    code_hash: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+struct ContractsResponse {
+    contract_infos: Vec<ContractEntry>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+struct ContractEntry {
+    contract_address: String,
+    contract_info: Value,
+}
+
+impl ContractEntry {
+    fn field(&self, name: &str) -> String {
+        match self.contract_info.get(name) {
+            Some(Value::String(string)) => string.clone(),
+            None | Some(Value::Null) => String::new(),
+            Some(other) => other.to_string(),
+        }
+    }
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ContractExplorer {
+    schema_text: String,
+    #[serde(skip)]
+    loaded_network: Option<Network>,
+    #[serde(skip)]
+    codes: Option<Pending<CodesResponse>>,
+    #[serde(skip)]
+    selected_code: Option<usize>,
+    #[serde(skip)]
+    contracts: Option<Pending<ContractsResponse>>,
+    #[serde(skip)]
+    selected_contract: Option<usize>,
+    /// A contract opened by address (e.g. from a link) rather than picked from the list.
+    #[serde(skip)]
+    linked_address: Option<String>,
+    #[serde(skip)]
+    linked_contract: Option<Pending<ContractEntry>>,
+    #[serde(skip)]
+    form: SchemaForm,
+    /// The network and address of the contract in the details, and the
+    /// [`JsonTree::generation`] of its info, bumped when it changes.
+    #[serde(skip)]
+    shown: Option<(Option<Network>, String)>,
+    #[serde(skip)]
+    shown_generation: u64,
+}
+
+impl ContractExplorer {
+    /// Shows the contract with the given address, whatever its code id.
+    pub fn open_contract(&mut self, address: String) {
+        self.selected_contract = None;
+        self.linked_address = Some(address);
+        self.linked_contract = None;
+    }
+
+    /// Address of the contract being shown, if any.
+    pub fn selected_address(&self) -> Option<String> {
+        self.list_contract()
+            .map(|contract| contract.contract_address)
+            .or_else(|| self.linked_address.clone())
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_contract = None;
+        self.linked_address = None;
+        self.linked_contract = None;
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, network: &mut Network, storage: &Storage) {
+        let t = Translator::load(ui.ctx());
+        ui.horizontal(|ui| {
+            ui.add(BidiLabel::new(t.get("contract-network")));
+            ComboBox::from_id_source("explorer_network")
+                .selected_text(network.to_string())
+                .show_ui(ui, |ui| {
+                    for option in Network::ALL {
+                        ui.selectable_value(network, option, option.to_string());
+                    }
+                });
+            if ui
+                .button(visual_order(&t.get("contract-refresh")))
+                .clicked()
+            {
+                self.loaded_network = None;
+            }
+        });
+        ui.separator();
+
+        if self.loaded_network != Some(*network) {
+            self.load_codes(ui.ctx(), *network, storage);
+        }
+        if let (Some(address), None) = (&self.linked_address, &self.linked_contract) {
+            self.linked_contract = Some(fetch_json(
+                ui.ctx(),
+                format!("{}/compute/v1beta1/info/{address}", network.lcd_url()),
+                storage,
+                format!("contracts/{}/{address}.json", network.chain_id()),
+            ));
+        }
+
+        change_animation_time(ui.ctx(), 0.30);
+        // The headers keep their state when the language changes.
+        CollapsingHeader::new(visual_order(&t.get("contract-codes")))
+            .id_source("Codes")
+            .default_open(true)
+            .show(ui, |ui| self.codes_ui(ui, *network, storage));
+        CollapsingHeader::new(visual_order(&t.get("contract-contracts")))
+            .id_source("Contracts")
+            .default_open(true)
+            .show(ui, |ui| self.contracts_ui(ui, &t));
+        CollapsingHeader::new(visual_order(&t.get("contract-details")))
+            .id_source("Details")
+            .default_open(true)
+            .show(ui, |ui| self.details_ui(ui, &t));
+        CollapsingHeader::new(visual_order(&t.get("contract-schema")))
+            .id_source("Schema")
+            .show(ui, |ui| self.schema_ui(ui, &t));
+        reset_animation_time(ui.ctx());
+    }
+
+    fn load_codes(&mut self, ctx: &egui::Context, network: Network, storage: &Storage) {
+        self.loaded_network = Some(network);
+        self.codes = Some(fetch_json(
+            ctx,
+            format!("{}/compute/v1beta1/codes", network.lcd_url()),
+            storage,
+            format!("contracts/{}/codes.json", network.chain_id()),
+        ));
+        self.selected_code = None;
+        self.contracts = None;
+        self.selected_contract = None;
+        // Look the linked contract up again on the new network.
+        self.linked_contract = None;
+    }
+
+    fn codes_ui(&mut self, ui: &mut Ui, network: Network, storage: &Storage) {
+        let Some(codes) = self.codes.clone() else {
+            return;
+        };
+        let mut clicked = None;
+        show_pending(ui, &codes, |ui, codes| {
+            let row_height = ui.text_style_height(&egui::TextStyle::Body);
+            ScrollArea::vertical()
+                .id_source("codes")
+                .max_height(160.0)
+                .show_rows(ui, row_height, codes.code_infos.len(), |ui, rows| {
+                    for index in rows {
+                        let code = &codes.code_infos[index];
+                        let text = format!("#{}  {}", code.code_id, short_hash(&code.code_hash));
+                        if ui
+                            .selectable_label(self.selected_code == Some(index), text)
+                            .clicked()
+                        {
+                            clicked = Some(code.code_id.clone());
+                            self.selected_code = Some(index);
+                        }
+                    }
+                });
+        });
+        if let Some(code_id) = clicked {
+            self.contracts = Some(fetch_json(
+                ui.ctx(),
+                format!(
+                    "{}/compute/v1beta1/contracts_by_code_id/{code_id}",
+                    network.lcd_url()
+                ),
+                storage,
+                format!("contracts/{}/code_{code_id}.json", network.chain_id()),
+            ));
+            self.selected_contract = None;
+        }
+    }
+
+    fn contracts_ui(&mut self, ui: &mut Ui, t: &Translator) {
+        let Some(contracts) = self.contracts.clone() else {
+            ui.add(BidiLabel::new(
+                RichText::new(t.get("contract-select-code")).weak(),
+            ));
+            return;
+        };
+        show_pending(ui, &contracts, |ui, contracts| {
+            let count = contracts.contract_infos.len();
+            ui.add(BidiLabel::new(
+                RichText::new(t.format("contract-count", &[("count", count.into())])).weak(),
+            ));
+            ScrollArea::vertical()
+                .id_source("contracts")
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for (index, contract) in contracts.contract_infos.iter().enumerate() {
+                        let text =
+                            format!("{}  {}", contract.field("label"), contract.contract_address);
+                        if ui
+                            .selectable_label(self.selected_contract == Some(index), text)
+                            .clicked()
+                        {
+                            self.selected_contract = Some(index);
+                            self.linked_address = None;
+                            self.linked_contract = None;
+                        }
+                    }
+                });
+        });
+    }
+
+    fn list_contract(&self) -> Option<ContractEntry> {
+        let contracts = self.contracts.as_ref()?.lock().unwrap();
+        let contracts = contracts.as_ref()?.as_ref().ok()?;
+        contracts
+            .contract_infos
+            .get(self.selected_contract?)
+            .cloned()
+    }
+
+    fn linked_contract(&self) -> Option<ContractEntry> {
+        let contract = self.linked_contract.as_ref()?.lock().unwrap();
+        contract.as_ref()?.as_ref().ok().cloned()
+    }
+
+    fn code(&self, code_id: Option<&str>) -> Option<CodeInfo> {
+        let codes = self.codes.as_ref()?.lock().unwrap();
+        let codes = codes.as_ref()?.as_ref().ok()?;
+        match code_id {
+            Some(code_id) => codes
+                .code_infos
+                .iter()
+                .find(|code| code.code_id == code_id)
+                .cloned(),
+            None => codes.code_infos.get(self.selected_code?).cloned(),
+        }
+    }
+
+    fn details_ui(&mut self, ui: &mut Ui, t: &Translator) {
+        if let (Some(linked), None) = (&self.linked_contract, self.selected_contract) {
+            // Spinner or error while the linked contract loads.
+            show_pending(ui, linked, |_, _| {});
+        }
+
+        let contract = self.list_contract().or_else(|| self.linked_contract());
+        let shown = contract
+            .as_ref()
+            .map(|contract| (self.loaded_network, contract.contract_address.clone()));
+        if shown != self.shown {
+            self.shown = shown;
+            self.shown_generation += 1;
+        }
+        let code = match &contract {
+            Some(contract) => self.code(Some(&contract.field("code_id"))),
+            None => self.code(None),
+        };
+        if code.is_none() && contract.is_none() {
+            ui.add(BidiLabel::new(
+                RichText::new(t.get("contract-nothing-selected")).weak(),
+            ));
+            return;
+        }
+
+        egui::Grid::new("contract_details")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                if let Some(code) = &code {
+                    ui.add(BidiLabel::new(t.get("contract-code-id")));
+                    ui.label(RichText::new(&code.code_id).app_style(ui, AppTextStyle::Numeric));
+                    ui.end_row();
+                    ui.add(BidiLabel::new(t.get("contract-code-hash")));
+                    ui.label(RichText::new(&code.code_hash).app_style(ui, AppTextStyle::MonoSmall));
+                    ui.end_row();
+                    ui.add(BidiLabel::new(t.get("contract-code-creator")));
+                    ui.monospace(&code.creator);
+                    ui.end_row();
+                }
+                if let Some(contract) = &contract {
+                    ui.add(BidiLabel::new(t.get("contract-address")));
+                    ui.monospace(&contract.contract_address);
+                    ui.end_row();
+                    ui.add(BidiLabel::new(t.get("contract-label")));
+                    ui.monospace(contract.field("label"));
+                    ui.end_row();
+                    ui.add(BidiLabel::new(t.get("contract-admin")));
+                    ui.monospace(contract.field("admin"));
+                    ui.end_row();
+                    ui.add(BidiLabel::new(t.get("contract-creator")));
+                    ui.monospace(contract.field("creator"));
+                    ui.end_row();
+                }
+            });
+
+        if let Some(contract) = &contract {
+            JsonTree::new("contract_info", &contract.contract_info)
+                .root_name("contract_info")
+                .searchable(true)
+                .generation(self.shown_generation)
+                .show(ui);
+        }
+    }
+
+    fn schema_ui(&mut self, ui: &mut Ui, t: &Translator) {
+        ui.add(BidiLabel::new(t.get("contract-schema-hint")));
+        ScrollArea::vertical()
+            .id_source("schema_text")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut self.schema_text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(4),
+                );
+            });
+        if self.schema_text.trim().is_empty() {
+            return;
+        }
+        match serde_json::from_str::<Value>(&self.schema_text) {
+            Ok(schema) => self.form.ui(ui, t, &schema),
+            Err(err) => {
+                let text = t.format(
+                    "contract-invalid-json",
+                    &[("error", (&err.to_string()).into())],
+                );
+                ui.add(BidiLabel::new(
+                    RichText::new(text).color(ui.visuals().error_fg_color),
+                ));
+            }
+        }
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..16).unwrap_or(hash)
+}
+
+//---------------------------------------------------------------
+// Message forms generated from cosmwasm JSON schemas.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FieldKind {
+    String,
+    Integer,
+    Boolean,
+    /// Anything more complex is entered as raw JSON.
+    Json,
+}
+
+#[derive(Clone, Debug)]
+struct Field {
+    name: String,
+    kind: FieldKind,
+    required: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Variant {
+    name: String,
+    /// `None` for variants that serialize as a bare string.
+    fields: Option<Vec<Field>>,
+}
+
+#[derive(Default)]
+struct SchemaForm {
+    section: String,
+    variant: String,
+    inputs: BTreeMap<String, String>,
+}
+
+impl SchemaForm {
+    fn ui(&mut self, ui: &mut Ui, t: &Translator, schema: &Value) {
+        // A full api schema (`cosmwasm-schema` >= 1.1) bundles one schema per entry point.
+        let sections: Vec<(&str, &Value)> = ["query", "execute"]
+            .into_iter()
+            .filter_map(|name| Some((name, schema.get(name).filter(|s| s.is_object())?)))
+            .collect();
+        let untitled = t.get("contract-message");
+        let sections = if sections.is_empty() {
+            let title = schema
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or(&untitled);
+            vec![(title, schema)]
+        } else {
+            sections
+        };
+
+        if !sections.iter().any(|(name, _)| *name == self.section) {
+            self.section = sections[0].0.to_owned();
+        }
+        ui.horizontal(|ui| {
+            for (name, _) in &sections {
+                ui.selectable_value(&mut self.section, (*name).to_owned(), visual_order(name));
+            }
+        });
+        let (_, section) = sections
+            .into_iter()
+            .find(|(name, _)| *name == self.section)
+            .unwrap();
+
+        let variants = variants(section);
+        if variants.is_empty() {
+            ui.add(BidiLabel::new(
+                RichText::new(t.get("contract-no-variants")).color(ui.visuals().warn_fg_color),
+            ));
+            return;
+        }
+        if !variants.iter().any(|variant| variant.name == self.variant) {
+            self.variant = variants[0].name.clone();
+            self.inputs.clear();
+        }
+
+        ComboBox::from_id_source("schema_variant")
+            .selected_text(&self.variant)
+            .show_ui(ui, |ui| {
+                for variant in &variants {
+                    if ui
+                        .selectable_value(&mut self.variant, variant.name.clone(), &variant.name)
+                        .changed()
+                    {
+                        self.inputs.clear();
+                    }
+                }
+            });
+        let variant = variants
+            .iter()
+            .find(|variant| variant.name == self.variant)
+            .unwrap();
+
+        if let Some(fields) = &variant.fields {
+            egui::Grid::new("schema_fields")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for field in fields {
+                        let label = if field.required {
+                            RichText::new(format!("{}*", field.name))
+                        } else {
+                            RichText::new(&field.name)
+                        };
+                        ui.label(label);
+                        let input = self.inputs.entry(field.name.clone()).or_default();
+                        match field.kind {
+                            FieldKind::Boolean => {
+                                let mut checked = input == "true";
+                                ui.checkbox(&mut checked, "");
+                                *input = checked.to_string();
+                            }
+                            FieldKind::Json => {
+                                ui.add(TextEdit::singleline(input).code_editor().hint_text("JSON"));
+                            }
+                            FieldKind::String | FieldKind::Integer => {
+                                ui.text_edit_singleline(input);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        match build_message(t, variant, &self.inputs) {
+            Ok(message) => {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(visual_order(&t.get("contract-copy-message")))
+                        .clicked()
+                    {
+                        ui.output_mut(|o| o.copied_text = message.to_string());
+                    }
+                    ui.add(BidiLabel::new(
+                        RichText::new(t.get("contract-send-hint")).weak(),
+                    ));
+                });
+                JsonTree::new("schema_message", &message)
+                    .root_name("message")
+                    .show(ui);
+            }
+            Err(err) => {
+                ui.add(BidiLabel::new(
+                    RichText::new(err).color(ui.visuals().error_fg_color),
+                ));
+            }
+        }
+    }
+}
+
+/// Follows a local `$ref` (e.g. `#/definitions/Uint128`) into the schema's definitions.
+fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+        .and_then(|name| root.get("definitions")?.get(name))
+        .unwrap_or(schema)
+}
+
+/// Lists the variants of an enum message schema (`oneOf` in cosmwasm schemas).
+fn variants(root: &Value) -> Vec<Variant> {
+    let mut variants = Vec::new();
+    let Some(options) = root.get("oneOf").and_then(Value::as_array) else {
+        return variants;
+    };
+    for option in options {
+        let option = resolve(option, root);
+        if let Some(names) = option.get("enum").and_then(Value::as_array) {
+            variants.extend(names.iter().filter_map(Value::as_str).map(|name| Variant {
+                name: name.to_owned(),
+                fields: None,
+            }));
+            continue;
+        }
+        let Some(properties) = option.get("properties").and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, body) in properties {
+            variants.push(Variant {
+                name: name.clone(),
+                fields: Some(fields(resolve(body, root), root)),
+            });
+        }
+    }
+    variants
+}
+
+fn fields(object: &Value, root: &Value) -> Vec<Field> {
+    let required: Vec<&str> = object
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    object
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| Field {
+                    name: name.clone(),
+                    kind: field_kind(property, root),
+                    required: required.contains(&name.as_str()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn field_kind(property: &Value, root: &Value) -> FieldKind {
+    // `Option<T>` shows up as `anyOf: [T, null]` or `type: [T, "null"]`.
+    if let Some(any_of) = property.get("anyOf").and_then(Value::as_array) {
+        let non_null: Vec<&Value> = any_of
+            .iter()
+            .filter(|option| option.get("type").and_then(Value::as_str) != Some("null"))
+            .collect();
+        return match non_null.as_slice() {
+            [inner] => field_kind(inner, root),
+            _ => FieldKind::Json,
+        };
+    }
+    let property = resolve(property, root);
+    let kind = match property.get("type") {
+        Some(Value::String(kind)) => Some(kind.as_str()),
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null"),
+        _ => None,
+    };
+    match kind {
+        Some("string") => FieldKind::String,
+        Some("integer") => FieldKind::Integer,
+        Some("boolean") => FieldKind::Boolean,
+        _ => FieldKind::Json,
+    }
+}
+
+fn build_message(
+    t: &Translator,
+    variant: &Variant,
+    inputs: &BTreeMap<String, String>,
+) -> Result<Value, String> {
+    let Some(fields) = &variant.fields else {
+        return Ok(Value::String(variant.name.clone()));
+    };
+    let mut body = Map::new();
+    for field in fields {
+        let input = inputs
+            .get(&field.name)
+            .map(String::as_str)
+            .unwrap_or_default();
+        let field_arg = [("field", (&field.name).into())];
+        let value = match field.kind {
+            FieldKind::Boolean => {
+                let checked = input == "true";
+                if !checked && !field.required {
+                    continue;
+                }
+                Value::Bool(checked)
+            }
+            _ if input.is_empty() => {
+                if field.required {
+                    return Err(t.format("contract-field-required", &field_arg));
+                }
+                continue;
+            }
+            FieldKind::String => Value::String(input.to_owned()),
+            FieldKind::Integer => {
+                integer(input).ok_or_else(|| t.format("contract-field-integer", &field_arg))?
+            }
+            FieldKind::Json => serde_json::from_str(input).map_err(|err| {
+                let error = err.to_string();
+                let args = [("field", (&field.name).into()), ("error", (&error).into())];
+                t.format("contract-field-json", &args)
+            })?,
+        };
+        body.insert(field.name.clone(), value);
+    }
+    let mut message = Map::new();
+    message.insert(variant.name.clone(), Value::Object(body));
+    Ok(Value::Object(message))
+}
+
+/// An integer field's value: a number while JSON numbers hold it exactly, and a string
+/// beyond, as CosmWasm takes `Uint128` and the like.
+fn integer(input: &str) -> Option<Value> {
+    let input = input.trim();
+    if let Ok(integer) = input.parse::<i64>() {
+        Some(Value::from(integer))
+    } else if let Ok(integer) = input.parse::<u64>() {
+        Some(Value::from(integer))
+    } else if input.parse::<u128>().is_ok() || input.parse::<i128>().is_ok() {
+        Some(Value::String(input.to_owned()))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_beyond_i64_are_kept() {
+        let variant = Variant {
+            name: "mint".to_owned(),
+            fields: Some(vec![Field {
+                name: "amount".to_owned(),
+                kind: FieldKind::Integer,
+                required: true,
+            }]),
+        };
+        let t = Translator::default();
+        let message = |amount: &str| {
+            let inputs = BTreeMap::from([("amount".to_owned(), amount.to_owned())]);
+            build_message(&t, &variant, &inputs).map(|message| message["mint"]["amount"].clone())
+        };
+        assert_eq!(message("-7"), Ok(Value::from(-7)));
+        assert_eq!(message("18446744073709551615"), Ok(Value::from(u64::MAX)));
+        assert_eq!(
+            message("340282366920938463463374607431768211455"),
+            Ok(Value::from("340282366920938463463374607431768211455"))
+        );
+        assert!(message("1.5").is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! WCAG contrast checks for the text colors of a theme, and a high-contrast variant of a
+//! theme that passes them.
+//!
+//! See <https://www.w3.org/TR/WCAG21/#contrast-minimum> for the ratios.
+
+use egui::style::{WidgetVisuals, Widgets};
+use egui::{Color32, Grid, Rgba, RichText, Ui, Visuals};
+
+use crate::direction::BidiLabel;
+use crate::i18n::Translator;
+
+/// Minimum contrast of normal text, WCAG level AA.
+pub const AA: f32 = 4.5;
+/// Minimum contrast of normal text, WCAG level AAA. What the high-contrast variant aims for.
+pub const AAA: f32 = 7.0;
+
+/// A text color of the theme and the background it is shown on.
+struct Pair {
+    message_id: &'static str,
+    foreground: fn(&mut Visuals) -> &mut Color32,
+    background: fn(&Visuals) -> Color32,
+}
+
+const PAIRS: &[Pair] = &[
+    Pair {
+        message_id: "contrast-text-on-panels",
+        foreground: |v| text_color(v, |w| &mut w.noninteractive),
+        background: |v| v.panel_fill,
+    },
+    Pair {
+        message_id: "contrast-text-in-windows",
+        foreground: |v| text_color(v, |w| &mut w.noninteractive),
+        background: |v| v.window_fill,
+    },
+    Pair {
+        message_id: "contrast-text-in-striped-rows",
+        foreground: |v| text_color(v, |w| &mut w.noninteractive),
+        background: |v| over(v.faint_bg_color, v.panel_fill),
+    },
+    Pair {
+        message_id: "contrast-code",
+        foreground: |v| text_color(v, |w| &mut w.noninteractive),
+        background: |v| v.code_bg_color,
+    },
+    Pair {
+        message_id: "contrast-text-fields",
+        foreground: |v| text_color(v, |w| &mut w.inactive),
+        background: |v| v.extreme_bg_color,
+    },
+    Pair {
+        message_id: "contrast-buttons",
+        foreground: |v| text_color(v, |w| &mut w.inactive),
+        background: |v| v.widgets.inactive.weak_bg_fill,
+    },
+    Pair {
+        message_id: "contrast-hovered-buttons",
+        foreground: |v| text_color(v, |w| &mut w.hovered),
+        background: |v| v.widgets.hovered.weak_bg_fill,
+    },
+    Pair {
+        message_id: "contrast-pressed-buttons",
+        foreground: |v| text_color(v, |w| &mut w.active),
+        background: |v| v.widgets.active.weak_bg_fill,
+    },
+    Pair {
+        message_id: "contrast-links-on-panels",
+        foreground: |v| &mut v.hyperlink_color,
+        background: |v| v.panel_fill,
+    },
+    Pair {
+        message_id: "contrast-links-in-windows",
+        foreground: |v| &mut v.hyperlink_color,
+        background: |v| v.window_fill,
+    },
+    Pair {
+        message_id: "contrast-warnings",
+        foreground: |v| &mut v.warn_fg_color,
+        background: |v| v.panel_fill,
+    },
+    Pair {
+        message_id: "contrast-errors",
+        foreground: |v| &mut v.error_fg_color,
+        background: |v| v.panel_fill,
+    },
+];
+
+/// egui uses `override_text_color` for all text when it's set, the widget's color otherwise.
+fn text_color(
+    visuals: &mut Visuals,
+    widget: fn(&mut Widgets) -> &mut WidgetVisuals,
+) -> &mut Color32 {
+    match &mut visuals.override_text_color {
+        Some(color) => color,
+        None => &mut widget(&mut visuals.widgets).fg_stroke.color,
+    }
+}
+
+/// The result of checking one [`Pair`].
+#[derive(Clone, Debug)]
+pub struct Check {
+    /// The message id of what is checked, e.g. "contrast-buttons".
+    pub message_id: &'static str,
+    pub foreground: Color32,
+    pub background: Color32,
+    pub ratio: f32,
+}
+
+impl Check {
+    pub fn passes(&self, required: f32) -> bool {
+        self.ratio >= required
+    }
+}
+
+/// Checks every text color of `visuals` against the background it is shown on.
+pub fn audit(visuals: &Visuals) -> Vec<Check> {
+    let mut visuals = visuals.clone(); // the foregrounds are only reachable mutably
+    PAIRS
+        .iter()
+        .map(|pair| {
+            let background = (pair.background)(&visuals);
+            let foreground = over(*(pair.foreground)(&mut visuals), background);
+            Check {
+                message_id: pair.message_id,
+                foreground,
+                background,
+                ratio: contrast_ratio(foreground, background),
+            }
+        })
+        .collect()
+}
+
+/// `visuals` with darker backgrounds (lighter in light mode), brighter borders, and text
+/// colors moved away from their backgrounds until they pass [`AAA`].
+pub fn high_contrast(visuals: &Visuals) -> Visuals {
+    let mut visuals = visuals.clone();
+    let (back, front) = if visuals.dark_mode {
+        (Color32::BLACK, Color32::WHITE)
+    } else {
+        (Color32::WHITE, Color32::BLACK)
+    };
+
+    for color in [
+        &mut visuals.panel_fill,
+        &mut visuals.window_fill,
+        &mut visuals.extreme_bg_color,
+        &mut visuals.code_bg_color,
+    ] {
+        *color = mix(*color, back, 0.5);
+    }
+    visuals.window_stroke.color = mix(visuals.window_stroke.color, front, 0.5);
+    let widgets = &mut visuals.widgets;
+    for widget in [
+        &mut widgets.noninteractive,
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+        &mut widgets.open,
+    ] {
+        widget.bg_fill = mix(widget.bg_fill, back, 0.5);
+        widget.weak_bg_fill = mix(widget.weak_bg_fill, back, 0.5);
+        widget.bg_stroke.color = mix(widget.bg_stroke.color, front, 0.5);
+    }
+
+    for pair in PAIRS {
+        let background = (pair.background)(&visuals);
+        let foreground = (pair.foreground)(&mut visuals);
+        // Each step moves a tenth of the way, so colors keep some of their hue.
+        for _ in 0..30 {
+            if contrast_ratio(over(*foreground, background), background) >= AAA {
+                break;
+            }
+            *foreground = mix(*foreground, front, 0.1);
+        }
+    }
+    visuals
+}
+
+/// From 1 (same color) to 21 (black on white).
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+fn relative_luminance(color: Color32) -> f32 {
+    let color = Rgba::from(color);
+    0.2126 * color.r() + 0.7152 * color.g() + 0.0722 * color.b()
+}
+
+/// `color` painted on an opaque `background`.
+fn over(color: Color32, background: Color32) -> Color32 {
+    let alpha = 1.0 - color.a() as f32 / 255.0;
+    let [r, g, b] = [0, 1, 2].map(|i| {
+        (color[i] as f32 + background[i] as f32 * alpha)
+            .round()
+            .min(255.0) as u8
+    });
+    Color32::from_rgb(r, g, b)
+}
+
+/// Moves `t` of the way from `a` to `b`.
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let [r, g, bl, alpha] =
+        [0, 1, 2, 3].map(|i| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).round() as u8);
+    Color32::from_rgba_premultiplied(r, g, bl, alpha)
+}
+
+/// Lists the checks of `visuals`, failures first.
+pub fn report_ui(ui: &mut Ui, t: &Translator, visuals: &Visuals) {
+    let mut checks = audit(visuals);
+    checks.sort_by(|a, b| a.ratio.total_cmp(&b.ratio));
+    let failures = checks.iter().filter(|check| !check.passes(AA)).count();
+    if failures == 0 {
+        ui.add(BidiLabel::new(t.get("contrast-all-pass")));
+    } else {
+        let args = [
+            ("failures", failures.into()),
+            ("count", checks.len().into()),
+            ("ratio", f64::from(AA).into()),
+        ];
+        ui.add(BidiLabel::new(
+            RichText::new(t.format("contrast-failures", &args)).color(visuals.warn_fg_color),
+        ));
+    }
+    ui.add_space(4.0);
+
+    Grid::new("contrast_report")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            for check in &checks {
+                let sample = RichText::new(" Aa ")
+                    .color(check.foreground)
+                    .background_color(check.background);
+                ui.label(sample);
+                ui.add(BidiLabel::new(t.get(check.message_id)));
+                ui.monospace(format!("{:5.2}:1", check.ratio));
+                if check.passes(AAA) {
+                    ui.label("AAA");
+                } else if check.passes(AA) {
+                    ui.label("AA");
+                } else {
+                    ui.add(BidiLabel::new(
+                        RichText::new(t.get("contrast-fails")).color(visuals.error_fg_color),
+                    ));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_passes(visuals: &Visuals, required: f32) {
+        let failures: Vec<_> = audit(visuals)
+            .into_iter()
+            .filter(|check| !check.passes(required))
+            .map(|check| format!("{} {:.2}:1", check.message_id, check.ratio))
+            .collect();
+        assert!(failures.is_empty(), "failing contrast: {failures:?}");
+    }
+
+    #[test]
+    fn contrast_ratios() {
+        assert!((contrast_ratio(Color32::BLACK, Color32::WHITE) - 21.0).abs() < 0.01);
+        assert_eq!(contrast_ratio(Color32::WHITE, Color32::WHITE), 1.0);
+        // The WCAG example of a gray that just passes AA on white.
+        let gray = Color32::from_gray(118);
+        assert!((contrast_ratio(gray, Color32::WHITE) - 4.54).abs() < 0.01);
+    }
+
+    #[test]
+    fn shipped_themes_pass() {
+        assert_passes(&crate::style::my_dark_visuals(), AA);
+        assert_passes(&crate::style::my_high_contrast_visuals(), AAA);
+    }
+
+    #[test]
+    fn high_contrast_of_other_themes() {
+        assert_passes(&high_contrast(&Visuals::dark()), AAA);
+        assert_passes(&high_contrast(&Visuals::light()), AAA);
+    }
+}
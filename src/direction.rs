@@ -0,0 +1,553 @@
+//! Left-to-right and right-to-left layouts.
+//!
+//! egui lays everything out left to right, so for right-to-left languages the app mirrors
+//! panel sides, anchors and rows itself with [`Direction`]. egui doesn't shape text or
+//! reorder it either; [`visual_order`] does the reordering for scripts like Hebrew that
+//! don't need shaping.
+//!
+//! Text is kept in logical order until it's laid out, and reordered one line at a time:
+//! widgets that show a single line, like buttons and window titles, take
+//! `visual_order(&text)`, and text that wraps goes through [`BidiLabel`], which wraps it
+//! first with [`wrap_rtl`].
+
+use std::borrow::Cow;
+
+use egui::panel::Side;
+use egui::text::LayoutJob;
+use egui::{
+    vec2, Align, Align2, FontSelection, InnerResponse, Label, Layout, Response, Ui, Vec2, Widget,
+    WidgetInfo, WidgetText, WidgetType,
+};
+
+use crate::i18n::Language;
+
+/// The layout direction the user picked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum DirectionSetting {
+    /// The direction of the language.
+    #[default]
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+
+impl DirectionSetting {
+    pub const ALL: [DirectionSetting; 3] = [
+        DirectionSetting::Auto,
+        DirectionSetting::LeftToRight,
+        DirectionSetting::RightToLeft,
+    ];
+
+    /// The id of its name in the message catalogs.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            DirectionSetting::Auto => "direction-auto",
+            DirectionSetting::LeftToRight => "direction-ltr",
+            DirectionSetting::RightToLeft => "direction-rtl",
+        }
+    }
+
+    pub fn resolve(self, language: Language) -> Direction {
+        match self {
+            DirectionSetting::Auto => language.direction(),
+            DirectionSetting::LeftToRight => Direction::LeftToRight,
+            DirectionSetting::RightToLeft => Direction::RightToLeft,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    pub fn is_rtl(self) -> bool {
+        self == Direction::RightToLeft
+    }
+
+    /// The direction the app is laid out in, for widgets that mirror themselves, like
+    /// [`crate::icons::IconButton`]. Left to right until [`Direction::store`] is called.
+    pub fn load(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|data| data.get_temp(direction_id()))
+            .unwrap_or_default()
+    }
+
+    pub fn store(self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(direction_id(), self));
+    }
+
+    /// Where a panel designed for left-to-right goes.
+    pub fn side(self, side: Side) -> Side {
+        match (self, side) {
+            (Direction::LeftToRight, side) => side,
+            (Direction::RightToLeft, Side::Left) => Side::Right,
+            (Direction::RightToLeft, Side::Right) => Side::Left,
+        }
+    }
+
+    /// Mirrors the horizontal part of an anchor or pivot, e.g. `RIGHT_TOP` to `LEFT_TOP`.
+    pub fn anchor(self, align: Align2) -> Align2 {
+        match self {
+            Direction::LeftToRight => align,
+            Direction::RightToLeft => Align2([flip(align.x()), align.y()]),
+        }
+    }
+
+    /// Mirrors the offset of an anchor.
+    pub fn offset(self, offset: Vec2) -> Vec2 {
+        match self {
+            Direction::LeftToRight => offset,
+            Direction::RightToLeft => Vec2::new(-offset.x, offset.y),
+        }
+    }
+
+    /// Like [`Ui::horizontal`], but starting at the right for right-to-left.
+    pub fn horizontal<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let layout = match self {
+            Direction::LeftToRight => Layout::left_to_right(Align::Center),
+            Direction::RightToLeft => Layout::right_to_left(Align::Center),
+        };
+        let size = vec2(
+            ui.available_size_before_wrap().x,
+            ui.spacing().interact_size.y,
+        );
+        ui.allocate_ui_with_layout(size, layout, add_contents)
+    }
+
+    /// A column, aligned to the left or right.
+    pub fn vertical(self) -> Layout {
+        match self {
+            Direction::LeftToRight => Layout::top_down(Align::Min),
+            Direction::RightToLeft => Layout::top_down(Align::Max),
+        }
+    }
+}
+
+fn direction_id() -> egui::Id {
+    egui::Id::new("layout_direction")
+}
+
+fn flip(align: Align) -> Align {
+    match align {
+        Align::Min => Align::Max,
+        Align::Center => Align::Center,
+        Align::Max => Align::Min,
+    }
+}
+
+/// Hebrew, Arabic and the other right-to-left scripts.
+fn is_rtl(c: char) -> bool {
+    matches!(c, '\u{0590}'..='\u{08FF}' | '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}')
+}
+
+/// Whether `text` has right-to-left letters, and reads right to left.
+pub fn has_rtl(text: &str) -> bool {
+    text.chars().any(is_rtl)
+}
+
+/// Left-to-right letters and digits, e.g. in `Setting1` or a path.
+fn is_ltr(c: char) -> bool {
+    c.is_alphanumeric() && !is_rtl(c)
+}
+
+/// Reorders right-to-left text so that egui, drawing left to right, shows it in reading
+/// order. Runs of left-to-right words and numbers inside it keep their order. Text that
+/// wraps has to be wrapped first, see [`wrap_rtl`].
+///
+/// This is a small part of the Unicode bidi algorithm, enough for UI labels. Letters aren't
+/// shaped, so Arabic shows its isolated forms.
+pub fn visual_order(text: &str) -> Cow<'_, str> {
+    if !has_rtl(text) {
+        return Cow::Borrowed(text);
+    }
+    let lines: Vec<String> = text.split('\n').map(visual_line).collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+fn visual_line(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    // Spans of chars, alternating right-to-left and left-to-right; a left-to-right span
+    // starts and ends with a letter or digit, and has no right-to-left letters.
+    let mut spans: Vec<(bool, &[char])> = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let ltr = is_ltr(chars[start]);
+        let end = if ltr {
+            let run = chars[start..]
+                .iter()
+                .position(|&c| is_rtl(c))
+                .map_or(chars.len(), |i| start + i);
+            // Neutrals at the end of the run belong to the right-to-left text after it.
+            start + chars[start..run].iter().rposition(|&c| is_ltr(c)).unwrap() + 1
+        } else {
+            chars[start..]
+                .iter()
+                .position(|&c| is_ltr(c))
+                .map_or(chars.len(), |i| start + i)
+        };
+        spans.push((ltr, &chars[start..end]));
+        start = end;
+    }
+
+    let mut out = String::with_capacity(line.len());
+    for (ltr, span) in spans.into_iter().rev() {
+        if ltr {
+            out.extend(span);
+        } else {
+            out.extend(span.iter().rev().map(|&c| mirror(c)));
+        }
+    }
+    out
+}
+
+/// A label for translated text: right-to-left text is wrapped in logical order, then each
+/// line is reordered with [`visual_order`] and aligned to the right. Other text is shown
+/// like [`Label`] shows it.
+///
+/// ```ignore
+/// ui.add(BidiLabel::new(RichText::new(t.get("links-social-hint")).weak()));
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct BidiLabel {
+    text: WidgetText,
+    wrap: Option<bool>,
+}
+
+impl BidiLabel {
+    pub fn new(text: impl Into<WidgetText>) -> Self {
+        Self {
+            text: text.into(),
+            wrap: None,
+        }
+    }
+
+    /// Like [`Label::wrap`].
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+}
+
+impl Widget for BidiLabel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        if !has_rtl(self.text.text()) {
+            let mut label = Label::new(self.text);
+            if let Some(wrap) = self.wrap {
+                label = label.wrap(wrap);
+            }
+            return ui.add(label);
+        }
+
+        let valign = ui.layout().vertical_align();
+        let text_job = self
+            .text
+            .into_text_job(ui.style(), FontSelection::Default, valign);
+        let logical = text_job.job.text;
+        let mut format = text_job
+            .job
+            .sections
+            .into_iter()
+            .next()
+            .map(|section| section.format)
+            .unwrap_or_default();
+        if !text_job.job_has_color {
+            format.color = ui.visuals().text_color();
+        }
+        let line_job = |text: String| LayoutJob::single_section(text, format.clone());
+
+        let width = if self.wrap.unwrap_or_else(|| ui.wrap_text()) {
+            ui.available_width()
+        } else {
+            f32::INFINITY
+        };
+        let lines = wrap_rtl(&[&logical], width, |_, piece| {
+            let galley = ui.fonts(|fonts| fonts.layout_job(line_job(piece.to_owned())));
+            galley.size().x
+        });
+        let mut lines = lines.into_iter().map(|line| {
+            line.into_iter()
+                .rev()
+                .map(|(_, piece)| piece)
+                .collect::<String>()
+        });
+        let response = if lines.len() == 1 {
+            ui.add(Label::new(line_job(lines.next().unwrap())).wrap(false))
+        } else {
+            ui.with_layout(Layout::top_down(Align::Max), |ui| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                for line in lines {
+                    ui.add(Label::new(line_job(line)).wrap(false));
+                }
+            })
+            .response
+        };
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Label, &logical));
+        response
+    }
+}
+
+/// Wraps right-to-left text to `width` and reorders it for display. The text is given in
+/// logical order as `sections`, e.g. the spans of a paragraph in different styles, and
+/// `measure` gives the width of a piece of a section. Newlines end a line.
+///
+/// Returns the lines top to bottom, each as pieces from right to left: the index of their
+/// section and their text in [`visual_order`]. Runs of left-to-right words, like `Rust 1.65`,
+/// aren't wrapped.
+pub fn wrap_rtl(
+    sections: &[&str],
+    width: f32,
+    mut measure: impl FnMut(usize, &str) -> f32,
+) -> Vec<Vec<(usize, String)>> {
+    let chars: Vec<(usize, char)> = sections
+        .iter()
+        .enumerate()
+        .flat_map(|(section, text)| text.chars().map(move |c| (section, c)))
+        .collect();
+
+    // The width of `chars[start..end]`, measured a section at a time.
+    let mut width_of = |start: usize, end: usize| -> f32 {
+        runs(&chars[start..end])
+            .map(|(section, text)| measure(section, &text))
+            .sum()
+    };
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start <= chars.len() {
+        let end = chars[start..]
+            .iter()
+            .position(|&(_, c)| c == '\n')
+            .map_or(chars.len(), |i| start + i);
+        let pieces = pieces(&chars[start..end]);
+
+        // The pieces of each line, as ranges of `chars`.
+        let mut line: Vec<(usize, usize)> = Vec::new();
+        let mut line_width = 0.0;
+        for (piece_start, piece_end) in pieces {
+            let (piece_start, piece_end) = (start + piece_start, start + piece_end);
+            let added = match line.last() {
+                Some(&(_, last_end)) => width_of(last_end, piece_end),
+                None => width_of(piece_start, piece_end),
+            };
+            if !line.is_empty() && line_width + added > width {
+                lines.push(visual_pieces(&chars, &line));
+                line.clear();
+                line_width = width_of(piece_start, piece_end);
+            } else {
+                line_width += added;
+            }
+            line.push((piece_start, piece_end));
+        }
+        lines.push(visual_pieces(&chars, &line));
+        start = end + 1;
+    }
+    lines
+}
+
+/// The words of a line that wrapping keeps together, as ranges of `chars`, in logical
+/// order. Left-to-right words, and the neutral words between them, are one piece, since
+/// they read left to right inside the right-to-left line.
+fn pieces(chars: &[(usize, char)]) -> Vec<(usize, usize)> {
+    let mut pieces: Vec<(usize, usize)> = Vec::new();
+    // The left-to-right piece that the next left-to-right word joins.
+    let mut ltr_piece: Option<usize> = None;
+    let mut start = 0;
+    while start < chars.len() {
+        if chars[start].1.is_whitespace() {
+            start += 1;
+            continue;
+        }
+        let end = chars[start..]
+            .iter()
+            .position(|&(_, c)| c.is_whitespace())
+            .map_or(chars.len(), |i| start + i);
+        let word = chars[start..end].iter().map(|&(_, c)| c);
+        if word.clone().any(is_rtl) {
+            pieces.push((start, end));
+            ltr_piece = None;
+        } else if word.clone().any(is_ltr) {
+            match ltr_piece {
+                Some(index) => {
+                    pieces.truncate(index + 1);
+                    pieces[index].1 = end;
+                }
+                None => {
+                    pieces.push((start, end));
+                    ltr_piece = Some(pieces.len() - 1);
+                }
+            }
+        } else {
+            pieces.push((start, end));
+        }
+        start = end;
+    }
+    pieces
+}
+
+/// A line of pieces from right to left, with the spaces between them.
+fn visual_pieces(chars: &[(usize, char)], line: &[(usize, usize)]) -> Vec<(usize, String)> {
+    let mut out = Vec::new();
+    for (i, &(start, end)) in line.iter().enumerate() {
+        if i > 0 {
+            out.extend(runs(&chars[line[i - 1].1..start]));
+        }
+        let piece = &chars[start..end];
+        if piece.iter().any(|&(_, c)| is_rtl(c)) {
+            out.extend(
+                runs(piece).map(|(section, text)| (section, visual_order(&text).into_owned())),
+            );
+        } else {
+            let mut runs: Vec<_> = runs(piece).collect();
+            runs.reverse();
+            out.extend(runs);
+        }
+    }
+    out
+}
+
+/// Splits chars into runs of the same section.
+fn runs(chars: &[(usize, char)]) -> impl Iterator<Item = (usize, String)> + '_ {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        let &(section, _) = chars.get(start)?;
+        let end = chars[start..]
+            .iter()
+            .position(|&(s, _)| s != section)
+            .map_or(chars.len(), |i| start + i);
+        let text = chars[start..end].iter().map(|&(_, c)| c).collect();
+        start = end;
+        Some((section, text))
+    })
+}
+
+/// Brackets point the other way in right-to-left text.
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn right_to_left_flips_sides_and_anchors() {
+        let rtl = Direction::RightToLeft;
+        assert_eq!(rtl.side(Side::Left), Side::Right);
+        assert_eq!(rtl.side(Side::Right), Side::Left);
+        assert_eq!(rtl.anchor(Align2::RIGHT_TOP), Align2::LEFT_TOP);
+        assert_eq!(rtl.anchor(Align2::RIGHT_BOTTOM), Align2::LEFT_BOTTOM);
+        assert_eq!(rtl.anchor(Align2::CENTER_TOP), Align2::CENTER_TOP);
+        assert_eq!(rtl.offset(Vec2::new(-12.0, 1.0)), Vec2::new(12.0, 1.0));
+
+        let ltr = Direction::LeftToRight;
+        assert_eq!(ltr.side(Side::Left), Side::Left);
+        assert_eq!(ltr.anchor(Align2::RIGHT_TOP), Align2::RIGHT_TOP);
+        assert_eq!(ltr.offset(Vec2::new(-12.0, 1.0)), Vec2::new(-12.0, 1.0));
+    }
+
+    #[test]
+    fn visual_order_of_mixed_text() {
+        assert_eq!(visual_order("Setting1: "), "Setting1: ");
+        assert_eq!(visual_order("שלום"), "םולש");
+        // The number and the Latin name keep their order, the rest is mirrored.
+        assert_eq!(visual_order("נוצרו 12 חוזים."), ".םיזוח 12 ורצונ");
+        assert_eq!(visual_order("הגדרה1: "), " :1הרדגה");
+        assert_eq!(visual_order("קוד (Rust)"), "(Rust) דוק");
+    }
+
+    #[test]
+    fn right_to_left_text_is_wrapped_before_it_is_reordered() {
+        let chars = |_: usize, text: &str| text.chars().count() as f32;
+        let lines = wrap_rtl(&["אחת שתיים שלוש"], 9.0, chars);
+        // The first words are on the first line, from right to left.
+        assert_eq!(
+            lines,
+            [
+                vec![
+                    (0, "תחא".to_owned()),
+                    (0, " ".to_owned()),
+                    (0, "םייתש".to_owned())
+                ],
+                vec![(0, "שולש".to_owned())],
+            ]
+        );
+
+        // Left-to-right words stay together and in order, in their own sections.
+        let lines = wrap_rtl(&["שלום ", "Rust 1.65", " עולם\nסוף"], 100.0, chars);
+        let texts: Vec<Vec<&str>> = lines
+            .iter()
+            .map(|line| line.iter().map(|(_, text)| text.as_str()).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            [vec!["םולש", " ", "Rust 1.65", " ", "םלוע"], vec!["ףוס"]]
+        );
+        assert_eq!(lines[0][2].0, 1);
+    }
+
+    #[test]
+    fn wrapped_right_to_left_labels_read_top_to_bottom() {
+        let message = "אחת שתיים שלוש ארבע חמש שש שבע שמונה תשע עשר";
+        let ctx = egui::Context::default();
+        ctx.set_fonts(crate::style::my_font_definitions());
+        let frame = || {
+            ctx.run(Default::default(), |ctx| {
+                // Like the popups of the tour.
+                egui::Area::new("popup").show(ctx, |ui| {
+                    ui.set_max_width(100.0);
+                    ui.add(BidiLabel::new(message));
+                });
+            })
+        };
+        // Areas are shown from their second frame.
+        frame();
+        let output = frame();
+        let mut lines: Vec<(f32, f32, String)> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.1 {
+                egui::Shape::Text(text) => Some((
+                    text.pos.y,
+                    text.galley.rect.translate(text.pos.to_vec2()).right(),
+                    text.galley.text().to_owned(),
+                )),
+                _ => None,
+            })
+            .collect();
+        lines.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert!(lines.len() > 2, "{lines:?}");
+
+        // The first line starts at the right with the first word, and the last line ends
+        // with the last word. All lines are aligned to the right.
+        let (_, right, first) = &lines[0];
+        assert!(first.ends_with("תחא"), "{first}");
+        assert!(lines.last().unwrap().2.starts_with("רשע"));
+        assert!(
+            lines.iter().all(|line| (line.1 - right).abs() < 1.0),
+            "{lines:?}"
+        );
+        // Reading the lines top to bottom, each from right to left, gives the message.
+        let read: Vec<String> = lines
+            .iter()
+            .map(|(_, _, line)| visual_order(line).into_owned())
+            .collect();
+        assert_eq!(read.join(" "), message);
+    }
+}
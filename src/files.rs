@@ -0,0 +1,131 @@
+//! Saving files: natively through a dialog asking where, off the frame loop, and on the web as
+//! a download. Used for screenshots and exported logs.
+
+use std::sync::{Arc, Mutex};
+
+use egui::Context;
+
+use crate::i18n::Translator;
+
+/// Where [`save_file`] saved the file, or `None` if the user cancelled.
+pub(crate) type Saved = Result<Option<String>, SaveError>;
+
+/// Why [`save_file`] failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum SaveError {
+    /// Writing the file the user picked failed.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    Write { path: String, error: String },
+    /// The browser didn't start the download.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    Download(String),
+}
+
+impl SaveError {
+    /// What to tell the user.
+    pub fn message(&self, t: &Translator) -> String {
+        match self {
+            SaveError::Write { path, error } => t.format(
+                "file-save-failed",
+                &[("path", path.into()), ("error", error.into())],
+            ),
+            SaveError::Download(error) => {
+                t.format("file-download-failed", &[("error", error.into())])
+            }
+        }
+    }
+}
+
+/// A file being saved by [`save_file`]. The dialog asking where to save it doesn't block
+/// the frame, so the result comes in a later one.
+#[derive(Clone, Default)]
+pub(crate) struct Saving(Arc<Mutex<Option<Saved>>>);
+
+impl Saving {
+    /// The result, once the file was saved or the user cancelled.
+    pub fn take(&self) -> Option<Saved> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Asks where to save `bytes` on native, offering `file_name` and only files matching
+/// `filter`, e.g. `("PNG image", &["png"])`. Downloads them as `file_name` on the web.
+pub(crate) fn save_file(
+    ctx: &Context,
+    file_name: &str,
+    filter: (&'static str, &'static [&'static str]),
+    bytes: Vec<u8>,
+) -> Saving {
+    let saving = Saving::default();
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let file_name = file_name.to_owned();
+        let (result, ctx) = (saving.clone(), ctx.clone());
+        // rfd's async dialogs may be opened from any thread; on macOS it moves them to the
+        // main thread itself.
+        std::thread::spawn(move || {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_file_name(&file_name)
+                .add_filter(filter.0, filter.1);
+            let saved = match pollster::block_on(dialog.save_file()) {
+                Some(file) => {
+                    let path = file.path().display().to_string();
+                    match std::fs::write(file.path(), bytes) {
+                        Ok(()) => Ok(Some(path)),
+                        Err(err) => Err(SaveError::Write {
+                            path,
+                            error: err.to_string(),
+                        }),
+                    }
+                }
+                None => Ok(None),
+            };
+            *result.0.lock().unwrap() = Some(saved);
+            ctx.request_repaint();
+        });
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (ctx, filter);
+        let saved = web::download(file_name, &bytes)
+            .map(|()| Some(file_name.to_owned()))
+            .map_err(SaveError::Download);
+        *saving.0.lock().unwrap() = Some(saved);
+    }
+    saving
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use wasm_bindgen::JsCast as _;
+
+    /// Offers `bytes` as a file download, by clicking a temporary link to a blob.
+    pub fn download(file_name: &str, bytes: &[u8]) -> Result<(), String> {
+        let js_error = |err: wasm_bindgen::JsValue| format!("{err:?}");
+
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or("no document")?;
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(js_error)?;
+        let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+
+        let link: web_sys::HtmlAnchorElement = document
+            .create_element("a")
+            .map_err(js_error)?
+            .unchecked_into();
+        link.set_href(&url);
+        link.set_download(file_name);
+        link.click();
+
+        // Some browsers only start reading the blob after the click handler returns.
+        let window = web_sys::window().ok_or("no window")?;
+        let revoke = wasm_bindgen::closure::Closure::once_into_js(move || {
+            web_sys::Url::revoke_object_url(&url).ok();
+        });
+        window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), 1000)
+            .map_err(js_error)?;
+        Ok(())
+    }
+}
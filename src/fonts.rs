@@ -0,0 +1,578 @@
+//! Fonts: the ones bundled with the app, plus fonts the user loads at runtime, e.g. for
+//! scripts the bundled ones don't cover.
+//!
+//! Which fonts each family uses, in fallback order, and the tweaks of each font come from
+//! the theme ([`crate::style::my_font_config`]) and can be changed in the "Fonts" window.
+//! Loaded fonts are kept in [`Storage`], so they are still there after a restart.
+//!
+//! Emoji are covered by egui's bundled fonts. Build with `--features cjk` to bundle a
+//! Chinese/Japanese/Korean fallback font too, see `fonts/README.md`.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use egui::{CollapsingHeader, FontData, FontDefinitions, FontFamily, FontTweak, RichText, Ui};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+use crate::storage::Storage;
+
+/// Which fonts to use for what. See [`crate::style::my_font_config`].
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct FontConfig {
+    /// Font names, most preferred first. Glyphs missing from a font are taken from the next.
+    pub families: Vec<(FontFamily, Vec<String>)>,
+    pub tweaks: BTreeMap<String, FontTweak>,
+}
+
+impl FontConfig {
+    pub fn fonts(&self, family: &FontFamily) -> &[String] {
+        self.families
+            .iter()
+            .find(|(f, _)| f == family)
+            .map_or(&[], |(_, fonts)| fonts.as_slice())
+    }
+
+    fn fonts_mut(&mut self, family: &FontFamily) -> Option<&mut Vec<String>> {
+        self.families
+            .iter_mut()
+            .find(|(f, _)| f == family)
+            .map(|(_, fonts)| fonts)
+    }
+}
+
+/// The fonts compiled into the app, by name.
+pub fn bundled_fonts() -> BTreeMap<String, FontData> {
+    let mut fonts = FontDefinitions::default().font_data; // egui's own, incl. emoji
+    fonts.insert(
+        "open_sans".to_owned(),
+        FontData::from_static(include_bytes!("../fonts/OpenSans-Regular.ttf")),
+    ); // .ttf and .otf supported
+    fonts.insert(
+        "fira_sans_bold".to_owned(),
+        FontData::from_static(include_bytes!("../fonts/FiraSans-Bold.ttf")),
+    );
+    #[cfg(feature = "cjk")]
+    fonts.insert(
+        CJK_FONT.to_owned(),
+        FontData::from_static(include_bytes!("../fonts/NotoSansSC-Regular.otf")),
+    );
+    fonts
+}
+
+/// Name of the font bundled by the `cjk` feature.
+pub const CJK_FONT: &str = "noto_sans_sc";
+
+/// Builds egui's font definitions from `config`. Fonts that aren't available are skipped.
+pub fn definitions(config: &FontConfig, mut fonts: BTreeMap<String, FontData>) -> FontDefinitions {
+    for (name, font) in &mut fonts {
+        if let Some(tweak) = config.tweaks.get(name) {
+            font.tweak = *tweak;
+        }
+    }
+    let mut families = BTreeMap::new();
+    for (family, names) in &config.families {
+        let names: Vec<String> = names
+            .iter()
+            .filter(|name| fonts.contains_key(*name))
+            .cloned()
+            .collect();
+        families.insert(family.clone(), names);
+    }
+    // egui needs these two, even if the config forgot them.
+    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+        let names = families.entry(family).or_default();
+        if names.is_empty() {
+            names.extend(fonts.keys().next().cloned());
+        }
+    }
+    FontDefinitions {
+        font_data: fonts,
+        families,
+    }
+}
+
+/// Checks that `bytes` is a font egui can use, since egui panics on invalid ones.
+pub fn validate(bytes: &[u8]) -> Result<(), String> {
+    ab_glyph::FontRef::try_from_slice(bytes)
+        .map(|_| ())
+        .map_err(|_| "not a TrueType or OpenType font".to_owned())
+}
+
+/// A font file that finished loading, or failed to.
+type Loaded = (String, Result<Vec<u8>, String>);
+
+/// Keeps the font config and the user's fonts, and applies them to the egui context.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct FontManager {
+    config: FontConfig,
+    /// Names of the fonts the user loaded; their data is in [`Storage`].
+    user_fonts: Vec<String>,
+    #[serde(skip)]
+    user_font_data: BTreeMap<String, FontData>,
+    #[serde(skip)]
+    inbox: Arc<Mutex<Vec<Loaded>>>,
+    #[serde(skip)]
+    changed: bool,
+    #[serde(skip)]
+    url: String,
+    #[serde(skip)]
+    preview: String,
+    /// Fonts that couldn't be loaded, and why.
+    #[serde(skip)]
+    errors: Vec<(String, String)>,
+    /// The keys of [`bundled_fonts`], which don't change.
+    #[serde(skip)]
+    bundled_names: Vec<String>,
+}
+
+impl Default for FontManager {
+    fn default() -> Self {
+        Self {
+            config: crate::style::my_font_config(),
+            user_fonts: Vec::new(),
+            user_font_data: BTreeMap::new(),
+            inbox: Default::default(),
+            changed: true,
+            url: String::new(),
+            preview: "The quick brown fox 0123 你好 こんにちは 안녕 🙂".to_owned(),
+            errors: Vec::new(),
+            bundled_names: bundled_fonts().into_keys().collect(),
+        }
+    }
+}
+
+impl FontManager {
+    /// Loads the user's fonts from `storage`; they are applied once they arrive.
+    pub fn start(&mut self, ctx: &egui::Context, storage: &Storage) {
+        self.changed = true;
+        for name in self.user_fonts.clone() {
+            let inbox = self.inbox.clone();
+            let ctx = ctx.clone();
+            storage.get(&storage_key(&name), move |bytes| {
+                let bytes = bytes.ok_or_else(|| "missing from storage".to_owned());
+                inbox.lock().unwrap().push((name, bytes));
+                ctx.request_repaint();
+            });
+        }
+    }
+
+    pub fn definitions(&self) -> FontDefinitions {
+        let mut fonts = bundled_fonts();
+        fonts.extend(self.user_font_data.clone());
+        definitions(&self.config, fonts)
+    }
+
+    /// Call once per frame: installs loaded fonts and applies changes.
+    pub fn update(&mut self, ctx: &egui::Context, storage: &Storage) {
+        let loaded = std::mem::take(&mut *self.inbox.lock().unwrap());
+        for (name, bytes) in loaded {
+            match bytes.and_then(|bytes| validate(&bytes).map(|()| bytes)) {
+                Ok(bytes) => self.add(name, bytes, storage),
+                Err(err) => {
+                    self.errors.push((name.clone(), err));
+                    // A stored font that no longer works.
+                    self.user_fonts.retain(|n| *n != name);
+                }
+            }
+        }
+        if std::mem::take(&mut self.changed) {
+            ctx.set_fonts(self.definitions());
+        }
+    }
+
+    /// The names of the fonts that couldn't be loaded since the last call, and why.
+    pub fn take_errors(&mut self) -> Vec<(String, String)> {
+        std::mem::take(&mut self.errors)
+    }
+
+    fn add(&mut self, name: String, bytes: Vec<u8>, storage: &Storage) {
+        if !self.user_fonts.contains(&name) {
+            storage.set(&storage_key(&name), bytes.clone());
+            self.user_fonts.push(name.clone());
+            // New fonts are the last fallback, until moved up.
+            for family in [FontFamily::Proportional, FontFamily::Monospace] {
+                if let Some(fonts) = self.config.fonts_mut(&family) {
+                    fonts.push(name.clone());
+                }
+            }
+        }
+        self.user_font_data
+            .insert(name, FontData::from_owned(bytes));
+        self.changed = true;
+    }
+
+    fn remove(&mut self, name: &str, storage: &Storage) {
+        storage.remove(&storage_key(name));
+        self.user_fonts.retain(|n| n != name);
+        self.user_font_data.remove(name);
+        for (_, fonts) in &mut self.config.families {
+            fonts.retain(|n| n != name);
+        }
+        self.changed = true;
+    }
+
+    /// Names a new font can't have.
+    fn taken_names(&self) -> Vec<String> {
+        self.user_fonts
+            .iter()
+            .cloned()
+            .chain(self.bundled_names.iter().cloned())
+            .collect()
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, t: &Translator, storage: &Storage) {
+        ui.horizontal(|ui| {
+            if ui
+                .button(visual_order(&t.get("fonts-load-ellipsis")))
+                .clicked()
+            {
+                self.pick_file(ui.ctx());
+            }
+            if ui.button(visual_order(&t.get("fonts-reset"))).clicked() {
+                let user_fonts = self.user_fonts.clone();
+                self.config = crate::style::my_font_config();
+                for name in user_fonts {
+                    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+                        if let Some(fonts) = self.config.fonts_mut(&family) {
+                            fonts.push(name.clone());
+                        }
+                    }
+                }
+                self.changed = true;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(BidiLabel::new(t.get("fonts-url")));
+            ui.text_edit_singleline(&mut self.url);
+            if ui
+                .add_enabled(
+                    !self.url.is_empty(),
+                    egui::Button::new(visual_order(&t.get("fonts-fetch"))),
+                )
+                .clicked()
+            {
+                self.fetch(ui.ctx());
+            }
+        });
+
+        ui.add_space(4.0);
+        ui.add(BidiLabel::new(t.get("fonts-preview")));
+        ui.text_edit_multiline(&mut self.preview);
+        ui.label(egui::RichText::new(&self.preview).monospace());
+
+        ui.add_space(4.0);
+        let available: Vec<String> = self
+            .bundled_names
+            .iter()
+            .cloned()
+            .chain(self.user_font_data.keys().cloned())
+            .collect();
+        let families: Vec<FontFamily> = self
+            .config
+            .families
+            .iter()
+            .map(|(family, _)| family.clone())
+            .collect();
+        for family in families {
+            let title = t.format("fonts-family", &[("family", (&family.to_string()).into())]);
+            CollapsingHeader::new(visual_order(&title))
+                .id_source(&family)
+                .show(ui, |ui| self.family_ui(ui, t, &family, &available));
+        }
+
+        CollapsingHeader::new(visual_order(&t.get("fonts-tweaks")))
+            .id_source("Tweaks")
+            .show(ui, |ui| {
+                egui::Grid::new("font_tweaks").striped(true).show(ui, |ui| {
+                    ui.add(BidiLabel::new(RichText::new(t.get("fonts-font")).strong()));
+                    ui.add(BidiLabel::new(RichText::new(t.get("fonts-scale")).strong()));
+                    ui.add(BidiLabel::new(
+                        RichText::new(t.get("fonts-y-offset-factor")).strong(),
+                    ));
+                    ui.add(BidiLabel::new(
+                        RichText::new(t.get("fonts-y-offset")).strong(),
+                    ));
+                    ui.end_row();
+                    for name in &available {
+                        // Kept only once edited, so the other fonts keep their own tweaks.
+                        let before = self.config.tweaks.get(name).copied().unwrap_or_default();
+                        let mut tweak = before;
+                        ui.label(name);
+                        ui.add(
+                            egui::DragValue::new(&mut tweak.scale)
+                                .speed(0.01)
+                                .clamp_range(0.1..=4.0),
+                        );
+                        ui.add(egui::DragValue::new(&mut tweak.y_offset_factor).speed(0.01));
+                        ui.add(egui::DragValue::new(&mut tweak.y_offset).speed(0.1));
+                        ui.end_row();
+                        if tweak != before {
+                            self.config.tweaks.insert(name.clone(), tweak);
+                            self.changed = true;
+                        }
+                    }
+                });
+            });
+
+        if !self.user_fonts.is_empty() {
+            CollapsingHeader::new(visual_order(&t.get("fonts-loaded")))
+                .id_source("Loaded fonts")
+                .show(ui, |ui| {
+                    for name in self.user_fonts.clone() {
+                        ui.horizontal(|ui| {
+                            ui.label(&name);
+                            if !self.user_font_data.contains_key(&name) {
+                                ui.spinner();
+                            }
+                            if ui
+                                .small_button(visual_order(&t.get("fonts-delete")))
+                                .clicked()
+                            {
+                                self.remove(&name, storage);
+                            }
+                        });
+                    }
+                });
+        }
+    }
+
+    fn family_ui(
+        &mut self,
+        ui: &mut Ui,
+        t: &Translator,
+        family: &FontFamily,
+        available: &[String],
+    ) {
+        let Some(fonts) = self.config.fonts_mut(family) else {
+            return;
+        };
+        enum Action {
+            Up(usize),
+            Down(usize),
+            Remove(usize),
+        }
+        let mut action = None;
+        let len = fonts.len();
+        for (i, name) in fonts.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}. {name}", i + 1));
+                if !available.contains(name) {
+                    ui.add(BidiLabel::new(
+                        RichText::new(t.get("fonts-not-available")).weak(),
+                    ));
+                }
+                if ui
+                    .add_enabled(i > 0, egui::Button::new("⬆").small())
+                    .clicked()
+                {
+                    action = Some(Action::Up(i));
+                }
+                if ui
+                    .add_enabled(i + 1 < len, egui::Button::new("⬇").small())
+                    .clicked()
+                {
+                    action = Some(Action::Down(i));
+                }
+                // egui needs at least one font per family.
+                if ui
+                    .add_enabled(len > 1, egui::Button::new("✖").small())
+                    .clicked()
+                {
+                    action = Some(Action::Remove(i));
+                }
+            });
+        }
+        match action {
+            Some(Action::Up(i)) => fonts.swap(i, i - 1),
+            Some(Action::Down(i)) => fonts.swap(i, i + 1),
+            Some(Action::Remove(i)) => {
+                fonts.remove(i);
+            }
+            None => {}
+        }
+
+        let mut add = None;
+        egui::ComboBox::from_id_source(("add_font", family))
+            .selected_text(visual_order(&t.get("fonts-add-fallback")))
+            .show_ui(ui, |ui| {
+                for name in available.iter().filter(|name| !fonts.contains(name)) {
+                    if ui.selectable_label(false, name).clicked() {
+                        add = Some(name.clone());
+                    }
+                }
+            });
+        fonts.extend(add);
+
+        if action.is_some() || fonts.len() != len {
+            self.changed = true;
+        }
+    }
+
+    fn fetch(&mut self, ctx: &egui::Context) {
+        let url = self.url.trim().to_owned();
+        let name = unique_name(
+            url.split(['?', '#']).next().unwrap_or(&url),
+            &self.taken_names(),
+        );
+        let inbox = self.inbox.clone();
+        let ctx = ctx.clone();
+        crate::http::get(url, move |bytes| {
+            inbox.lock().unwrap().push((name, bytes));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Asks for a font file on a thread of its own, like [`crate::files::save_file`], so
+    /// the frames go on while the dialog is open.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn pick_file(&mut self, ctx: &egui::Context) {
+        let inbox = self.inbox.clone();
+        let ctx = ctx.clone();
+        let taken = self.taken_names();
+        std::thread::spawn(move || {
+            let dialog = rfd::AsyncFileDialog::new().add_filter("Fonts", &["ttf", "otf"]);
+            let Some(file) = pollster::block_on(dialog.pick_file()) else {
+                return;
+            };
+            let path = file.path();
+            let name = unique_name(&path.to_string_lossy(), &taken);
+            let bytes = std::fs::read(path)
+                .map_err(|err| format!("failed to read {}: {err}", path.display()));
+            inbox.lock().unwrap().push((name, bytes));
+            ctx.request_repaint();
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn pick_file(&mut self, ctx: &egui::Context) {
+        let inbox = self.inbox.clone();
+        let ctx = ctx.clone();
+        let taken = self.taken_names();
+        web::upload(".ttf,.otf", move |file_name, bytes| {
+            inbox
+                .lock()
+                .unwrap()
+                .push((unique_name(&file_name, &taken), bytes));
+            ctx.request_repaint();
+        });
+    }
+}
+
+/// A name for a font loaded from `file_name` that isn't in `taken`, e.g. "NotoSansJP".
+fn unique_name(file_name: &str, taken: &[String]) -> String {
+    let stem = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(file_name)
+        .split('.')
+        .next()
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("font");
+    let mut name = stem.to_owned();
+    let mut i = 2;
+    while taken.contains(&name) {
+        name = format!("{stem} ({i})");
+        i += 1;
+    }
+    name
+}
+
+fn storage_key(name: &str) -> String {
+    format!("fonts/{name}")
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use wasm_bindgen::{closure::Closure, JsCast as _};
+
+    /// Lets the user pick a file and reads it.
+    pub fn upload(accept: &str, on_done: impl 'static + FnOnce(String, Result<Vec<u8>, String>)) {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let Ok(input) = document.create_element("input") else {
+            return;
+        };
+        let input: web_sys::HtmlInputElement = input.unchecked_into();
+        input.set_type("file");
+        input.set_accept(accept);
+
+        let on_change = {
+            let input = input.clone();
+            Closure::once(move || {
+                let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                    return;
+                };
+                wasm_bindgen_futures::spawn_local(async move {
+                    let bytes = wasm_bindgen_futures::JsFuture::from(file.array_buffer())
+                        .await
+                        .map(|buffer| js_sys::Uint8Array::new(&buffer).to_vec())
+                        .map_err(|err| format!("{err:?}"));
+                    on_done(file.name(), bytes);
+                });
+            })
+        };
+        input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+        on_change.forget();
+        input.click();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_fonts_become_fallbacks() {
+        let ctx = egui::Context::default();
+        let storage = Storage::default();
+        let mut manager = FontManager::default();
+
+        let bytes = include_bytes!("../fonts/FiraSans-Bold.ttf").to_vec();
+        let name = unique_name("/home/me/FiraSans-Bold.ttf", &manager.taken_names());
+        assert_eq!(name, "FiraSans-Bold");
+        manager.inbox.lock().unwrap().extend([
+            (name.clone(), Ok(bytes)),
+            ("broken".to_owned(), Ok(b"not a font".to_vec())),
+        ]);
+        manager.update(&ctx, &storage);
+
+        assert_eq!(manager.user_fonts, std::slice::from_ref(&name));
+        assert_eq!(manager.take_errors().len(), 1);
+        let definitions = manager.definitions();
+        assert_eq!(
+            definitions.families[&FontFamily::Proportional].last(),
+            Some(&name)
+        );
+        // Not bundled without the `cjk` feature, so left out.
+        for fonts in definitions.families.values() {
+            assert!(fonts
+                .iter()
+                .all(|font| definitions.font_data.contains_key(font)));
+        }
+
+        manager.remove(&name, &storage);
+        assert!(!manager.definitions().font_data.contains_key(&name));
+    }
+
+    #[test]
+    fn showing_tweaks_leaves_them_alone() {
+        let ctx = egui::Context::default();
+        let storage = Storage::default();
+        let mut manager = FontManager::default();
+        let bytes = include_bytes!("../fonts/FiraSans-Bold.ttf").to_vec();
+        manager.add("mine".to_owned(), bytes, &storage);
+        let tweaks = manager.config.tweaks.clone();
+
+        // Opens the "Tweaks" header.
+        ctx.memory_mut(|memory| memory.set_everything_is_visible(true));
+        let _ = ctx.run(Default::default(), |ctx| {
+            let t = Translator::default();
+            egui::CentralPanel::default().show(ctx, |ui| manager.ui(ui, &t, &storage));
+        });
+        assert_eq!(manager.config.tweaks, tweaks);
+        assert!(!manager.config.tweaks.contains_key("mine"));
+    }
+}
@@ -0,0 +1,287 @@
+//! Runs [`TemplateApp`] on a headless `egui::Context` for tests, so they need neither a
+//! window nor a GPU.
+//!
+//! ```ignore
+//! let mut harness = Harness::new();
+//! harness.click_label("Button");
+//! assert!(harness.app.alert_window_open);
+//! harness.settle();
+//! harness.assert_snapshot("alert");
+//! ```
+//!
+//! Snapshots are checked in under `tests/snapshots`. After an intended change to the look
+//! of the app, update them with `UPDATE_SNAPSHOTS=1 cargo test`.
+
+use std::path::{Path, PathBuf};
+
+use egui::{Event, Key, Modifiers, PointerButton, Pos2, Rect, Shape, Vec2};
+
+use crate::raster::Rasterizer;
+use crate::TemplateApp;
+
+/// Time between frames, as if running at 60 fps.
+const FRAME_TIME: f64 = 1.0 / 60.0;
+
+/// How much a channel of a pixel may differ from the snapshot, e.g. due to float rounding.
+const CHANNEL_TOLERANCE: u8 = 8;
+/// How many pixels may differ by more than that.
+const PIXEL_TOLERANCE: usize = 16;
+
+pub struct Harness {
+    pub ctx: egui::Context,
+    pub app: TemplateApp,
+    /// The URLs the app asked to open, oldest first.
+    pub opened_urls: Vec<String>,
+    /// Input for the next frame.
+    input: egui::RawInput,
+    output: egui::FullOutput,
+    rasterizer: Rasterizer,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self::with_size(egui::vec2(1024.0, 768.0))
+    }
+
+    /// An app with default state, in a window of `size` points.
+    pub fn with_size(size: Vec2) -> Self {
+        let ctx = egui::Context::default();
+        crate::app::configure_context(&ctx, None);
+        let mut harness = Self {
+            ctx,
+            app: TemplateApp::default(),
+            opened_urls: Vec::new(),
+            input: egui::RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, size)),
+                pixels_per_point: Some(1.0),
+                time: Some(0.0),
+                // Like a window in the background, so the background pattern holds still.
+                has_focus: false,
+                ..Default::default()
+            },
+            output: Default::default(),
+            rasterizer: Rasterizer::default(),
+        };
+        harness.run();
+        harness
+    }
+
+    /// Runs a single frame with the queued input.
+    pub fn run(&mut self) -> &egui::FullOutput {
+        let mut input = self.input.take();
+        input.time = self.input.time;
+        self.input.time = Some(input.time.unwrap_or_default() + FRAME_TIME);
+        self.input.screen_rect = input.screen_rect;
+        self.input.pixels_per_point = input.pixels_per_point;
+        self.output = self.ctx.run(input, |ctx| self.app.ui(ctx));
+        self.rasterizer.update_textures(&self.output.textures_delta);
+        if let Some(open) = &self.output.platform_output.open_url {
+            self.opened_urls.push(open.url.clone());
+        }
+        &self.output
+    }
+
+    /// Runs frames until animations have finished.
+    pub fn settle(&mut self) {
+        for _ in 0..120 {
+            if !self.run().repaint_after.is_zero() {
+                return;
+            }
+        }
+        panic!("the app kept repainting for 120 frames");
+    }
+
+    /// The shapes painted by the last frame.
+    pub fn shapes(&self) -> &[epaint::ClippedShape] {
+        &self.output.shapes
+    }
+
+    /// Screen rects of all visible texts that read exactly `label`.
+    pub fn find_all(&self, label: &str) -> Vec<Rect> {
+        fn visit(shape: &Shape, clip_rect: Rect, label: &str, found: &mut Vec<Rect>) {
+            match shape {
+                Shape::Vec(shapes) => {
+                    for shape in shapes {
+                        visit(shape, clip_rect, label, found);
+                    }
+                }
+                Shape::Text(text) if text.galley.text() == label => {
+                    let rect = text.galley.rect.translate(text.pos.to_vec2());
+                    if clip_rect.intersects(rect) {
+                        found.push(rect.intersect(clip_rect));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut found = Vec::new();
+        for epaint::ClippedShape(clip_rect, shape) in self.shapes() {
+            visit(shape, *clip_rect, label, &mut found);
+        }
+        found
+    }
+
+    /// The screen rect of the text `label`, if it is shown exactly once.
+    pub fn find(&self, label: &str) -> Option<Rect> {
+        match self.find_all(label).as_slice() {
+            [rect] => Some(*rect),
+            [] => None,
+            rects => panic!("{label:?} is shown {} times", rects.len()),
+        }
+    }
+
+    /// Like [`Self::find`], but panics if `label` isn't shown.
+    pub fn rect(&self, label: &str) -> Rect {
+        self.find(label)
+            .unwrap_or_else(|| panic!("{label:?} is not shown"))
+    }
+
+    /// Clicks at `pos`: runs a frame for the press, one for the release, and one more so
+    /// what the click opened (e.g. a menu) is shown.
+    pub fn click(&mut self, pos: Pos2) {
+        self.pointer_button(pos, true);
+        self.run();
+        self.pointer_button(pos, false);
+        self.run();
+        self.run();
+    }
+
+    /// Moves the pointer to `pos`.
+    pub fn hover(&mut self, pos: Pos2) {
+        self.input.events.push(Event::PointerMoved(pos));
+        self.run();
+    }
+
+    /// Clicks the middle of the text `label`, e.g. a button.
+    pub fn click_label(&mut self, label: &str) {
+        self.click(self.rect(label).center());
+    }
+
+    /// Types `text` into the focused widget.
+    pub fn type_text(&mut self, text: &str) {
+        self.input.events.push(Event::Text(text.to_owned()));
+        self.run();
+    }
+
+    /// Presses and releases `key`.
+    pub fn key(&mut self, key: Key, modifiers: Modifiers) {
+        for pressed in [true, false] {
+            self.input.events.push(Event::Key {
+                key,
+                pressed,
+                repeat: false,
+                modifiers,
+            });
+        }
+        self.input.modifiers = modifiers;
+        self.run();
+        self.input.modifiers = Modifiers::NONE;
+    }
+
+    fn pointer_button(&mut self, pos: Pos2, pressed: bool) {
+        self.input.events.push(Event::PointerMoved(pos));
+        self.input.events.push(Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: self.input.modifiers,
+        });
+    }
+
+    /// Paints the last frame on the CPU.
+    pub fn render(&self) -> image::RgbaImage {
+        let pixels_per_point = self.ctx.pixels_per_point();
+        let size = self.ctx.screen_rect().size() * pixels_per_point;
+        self.rasterizer.paint(
+            &self.ctx.tessellate(self.output.shapes.clone()),
+            pixels_per_point,
+            [size.x.round() as u32, size.y.round() as u32],
+        )
+    }
+
+    /// Compares the last frame with `tests/snapshots/{name}.png`.
+    ///
+    /// On failure, the new image and an image of the differences are written to
+    /// `target/snapshots`.
+    ///
+    /// Only for debug builds, since release builds don't show the debug build warning. Mark
+    /// snapshot tests `#[cfg_attr(not(debug_assertions), ignore)]`.
+    pub fn assert_snapshot(&self, name: &str) {
+        if !cfg!(debug_assertions) {
+            panic!("snapshot {name} is of a debug build, ignore its test in release builds");
+        }
+        let image = self.render();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let path = root.join("tests/snapshots").join(format!("{name}.png"));
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            save(&image, &path);
+            return;
+        }
+        let expected = match image::open(&path) {
+            Ok(expected) => expected.into_rgba8(),
+            Err(err) => {
+                let new = failure_path(name, "new");
+                save(&image, &new);
+                panic!(
+                    "failed to open snapshot {}: {err}\nthe new image is at {}\nrun with UPDATE_SNAPSHOTS=1 to accept it",
+                    path.display(),
+                    new.display()
+                );
+            }
+        };
+        if expected.dimensions() != image.dimensions() {
+            let new = failure_path(name, "new");
+            save(&image, &new);
+            panic!(
+                "snapshot {name} is {:?} but the frame is {:?}; the new image is at {}",
+                expected.dimensions(),
+                image.dimensions(),
+                new.display()
+            );
+        }
+
+        let mut diff = image::RgbaImage::new(image.width(), image.height());
+        let mut differing = 0;
+        for ((new, old), dst) in image.pixels().zip(expected.pixels()).zip(diff.pixels_mut()) {
+            let differs = new
+                .0
+                .iter()
+                .zip(old.0)
+                .any(|(new, old)| new.abs_diff(old) > CHANNEL_TOLERANCE);
+            *dst = if differs {
+                differing += 1;
+                image::Rgba([255, 0, 0, 255])
+            } else {
+                // Faded, so the differences stand out.
+                let [r, g, b, _] = old.0;
+                image::Rgba([r / 4, g / 4, b / 4, 255])
+            };
+        }
+        if differing > PIXEL_TOLERANCE {
+            let new = failure_path(name, "new");
+            let diff_path = failure_path(name, "diff");
+            save(&image, &new);
+            save(&diff, &diff_path);
+            panic!(
+                "{differing} pixels differ from snapshot {name}\nnew image: {}\ndifferences: {}\nrun with UPDATE_SNAPSHOTS=1 if the change is intended",
+                new.display(),
+                diff_path.display()
+            );
+        }
+    }
+}
+
+fn failure_path(name: &str, kind: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target/snapshots")
+        .join(format!("{name}.{kind}.png"))
+}
+
+fn save(image: &image::RgbaImage, path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    image
+        .save(path)
+        .unwrap_or_else(|err| panic!("failed to save {}: {err}", path.display()));
+}
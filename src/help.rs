@@ -0,0 +1,291 @@
+//! The help window: topics from `assets/help/`, the keyboard shortcuts, the release notes of
+//! this version and the About page.
+//!
+//! The release notes are the section of `CHANGELOG.md` for this version, extracted by
+//! `build.rs`. They open by themselves on the first start after an update. The topics are
+//! markdown and only in English for now.
+
+use egui::{
+    Grid, InnerResponse, Key, KeyboardShortcut, Modifiers, RichText, ScrollArea, TextEdit, Ui,
+    Window,
+};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+use crate::markdown::Markdown;
+use crate::text_styles::{AppTextStyle, RichTextExt};
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+const GIT_COMMIT: &str = env!("GIT_COMMIT");
+const BUILD_DATE: &str = env!("BUILD_DATE");
+const WHATS_NEW: &str = include_str!(concat!(env!("OUT_DIR"), "/whats_new.md"));
+
+/// The help topics, in the order they are listed. Each starts with a `# Title` line.
+const TOPICS: [&str; 5] = [
+    include_str!("../assets/help/getting-started.md"),
+    include_str!("../assets/help/contracts.md"),
+    include_str!("../assets/help/appearance.md"),
+    include_str!("../assets/help/screenshots.md"),
+    include_str!("../assets/help/data.md"),
+];
+
+/// The fonts in [`crate::fonts::bundled_fonts`] and their licenses.
+const FONT_LICENSES: &[(&str, &str)] = &[
+    ("open_sans", "Open Sans: SIL Open Font License 1.1"),
+    ("fira_sans_bold", "Fira Sans: SIL Open Font License 1.1"),
+    ("Hack", "Hack: MIT License and Bitstream Vera License"),
+    ("Ubuntu-Light", "Ubuntu: Ubuntu Font Licence 1.0"),
+    ("NotoEmoji-Regular", "Noto Emoji: SIL Open Font License 1.1"),
+    ("emoji-icon-font", "emoji-icon-font: MIT License"),
+    (
+        crate::fonts::CJK_FONT,
+        "Noto Sans SC: SIL Open Font License 1.1",
+    ),
+];
+const OFL: &str = include_str!("../fonts/OFL.txt");
+
+/// Opens the help topics.
+pub const OPEN_HELP: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F1);
+
+/// The keyboard shortcuts of the app, with the message ids of what they do.
+fn shortcuts() -> Vec<(KeyboardShortcut, &'static str)> {
+    let mut shortcuts = vec![
+        (OPEN_HELP, "shortcut-help"),
+        (
+            KeyboardShortcut::new(Modifiers::NONE, Key::Escape),
+            "shortcut-close-tour",
+        ),
+    ];
+    // The browser zooms on the web.
+    if cfg!(not(target_arch = "wasm32")) {
+        use egui::gui_zoom::kb_shortcuts;
+        shortcuts.extend([
+            (kb_shortcuts::ZOOM_IN, "shortcut-zoom-in"),
+            (kb_shortcuts::ZOOM_OUT, "shortcut-zoom-out"),
+            (kb_shortcuts::ZOOM_RESET, "shortcut-zoom-reset"),
+        ]);
+    }
+    shortcuts
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Page {
+    #[default]
+    Topics,
+    Shortcuts,
+    WhatsNew,
+    About,
+}
+
+impl Page {
+    pub const ALL: [Page; 4] = [Page::Topics, Page::Shortcuts, Page::WhatsNew, Page::About];
+
+    /// The id of its name in the message catalogs.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Page::Topics => "help-topics",
+            Page::Shortcuts => "keyboard-shortcuts",
+            Page::WhatsNew => "whats-new",
+            Page::About => "about",
+        }
+    }
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Help {
+    /// The version that ran last, to show what's new after an update. `None` on the first
+    /// start, which gets the tour instead.
+    last_version: Option<String>,
+    #[serde(skip)]
+    open: bool,
+    #[serde(skip)]
+    page: Page,
+    #[serde(skip)]
+    query: String,
+    /// Index in [`TOPICS`].
+    #[serde(skip)]
+    topic: usize,
+    #[serde(skip)]
+    started: bool,
+}
+
+impl Help {
+    pub fn open(&mut self, page: Page) {
+        self.open = true;
+        self.page = page;
+    }
+
+    /// On the first frame, opens the help at the release notes if this version is new. F1
+    /// opens it at the topics.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        if !self.started {
+            self.started = true;
+            if self
+                .last_version
+                .as_deref()
+                .map_or(false, |last| last != VERSION)
+            {
+                self.open(Page::WhatsNew);
+            }
+            self.last_version = Some(VERSION.to_owned());
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&OPEN_HELP)) {
+            self.open(Page::Topics);
+        }
+    }
+
+    /// Shows the help window, if it is open.
+    pub fn ui(&mut self, ctx: &egui::Context, t: &Translator) -> Option<InnerResponse<Option<()>>> {
+        let mut open = self.open;
+        let response = Window::new(visual_order(&t.get("menu-help")))
+            .id(egui::Id::new("Help"))
+            .open(&mut open)
+            .default_size([560.0, 400.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for page in Page::ALL {
+                        ui.selectable_value(
+                            &mut self.page,
+                            page,
+                            visual_order(&t.get(page.message_id())),
+                        );
+                    }
+                });
+                ui.separator();
+                match self.page {
+                    Page::Topics => self.topics_ui(ui, t),
+                    Page::Shortcuts => shortcuts_ui(ui, t),
+                    Page::WhatsNew => {
+                        ScrollArea::vertical().show(ui, |ui| {
+                            Markdown::new("whats_new", WHATS_NEW).show(ui);
+                        });
+                    }
+                    Page::About => about_ui(ui, t),
+                }
+            });
+        self.open = open;
+        response
+    }
+
+    fn topics_ui(&mut self, ui: &mut Ui, t: &Translator) {
+        let matches = search(&self.query);
+        if !matches.contains(&self.topic) {
+            if let Some(&first) = matches.first() {
+                self.topic = first;
+            }
+        }
+        ui.horizontal_top(|ui| {
+            ui.vertical(|ui| {
+                ui.set_width(160.0);
+                ui.add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text(visual_order(&t.get("help-search")))
+                        .desired_width(f32::INFINITY),
+                );
+                for &index in &matches {
+                    ui.selectable_value(&mut self.topic, index, title(TOPICS[index]));
+                }
+            });
+            ui.separator();
+            ScrollArea::vertical().show(ui, |ui| {
+                if matches.is_empty() {
+                    let query = &self.query;
+                    ui.add(BidiLabel::new(
+                        RichText::new(t.format("help-no-results", &[("query", query.into())]))
+                            .weak(),
+                    ));
+                } else {
+                    Markdown::new(("help_topic", self.topic), TOPICS[self.topic]).show(ui);
+                }
+            });
+        });
+    }
+}
+
+/// The title of a topic, from its first line.
+fn title(topic: &str) -> &str {
+    let first = topic.lines().next().unwrap_or_default();
+    first.trim_start_matches('#').trim()
+}
+
+/// The indices of the topics containing `query`, ignoring case.
+fn search(query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    (0..TOPICS.len())
+        .filter(|&index| TOPICS[index].to_lowercase().contains(&query))
+        .collect()
+}
+
+fn shortcuts_ui(ui: &mut Ui, t: &Translator) {
+    Grid::new("shortcuts").striped(true).show(ui, |ui| {
+        for (shortcut, message_id) in shortcuts() {
+            ui.add(BidiLabel::new(t.get(message_id)));
+            ui.label(RichText::new(ui.ctx().format_shortcut(&shortcut)).monospace());
+            ui.end_row();
+        }
+    });
+}
+
+fn about_ui(ui: &mut Ui, t: &Translator) {
+    let name = RichText::new(env!("CARGO_PKG_NAME"));
+    ui.label(name.app_style(ui, AppTextStyle::Title));
+    Grid::new("about").show(ui, |ui| {
+        ui.add(BidiLabel::new(t.get("about-version")));
+        ui.label(VERSION);
+        ui.end_row();
+        ui.add(BidiLabel::new(t.get("about-commit")));
+        ui.label(RichText::new(GIT_COMMIT).app_style(ui, AppTextStyle::Numeric));
+        ui.end_row();
+        ui.add(BidiLabel::new(t.get("about-build-date")));
+        ui.label(BUILD_DATE);
+        ui.end_row();
+    });
+    ui.add_space(8.0);
+    ui.add(BidiLabel::new(
+        RichText::new(t.get("about-fonts")).app_style(ui, AppTextStyle::Subtitle),
+    ));
+    let fonts = crate::fonts::bundled_fonts();
+    for (font, license) in FONT_LICENSES {
+        if fonts.contains_key(*font) {
+            ui.label(*license);
+        }
+    }
+    egui::CollapsingHeader::new(visual_order(&t.get("about-ofl"))).show(ui, |ui| {
+        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            ui.label(RichText::new(OFL).app_style(ui, AppTextStyle::MonoSmall));
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_are_found_by_title_and_text() {
+        assert!(TOPICS.iter().all(|topic| topic.starts_with("# ")));
+        assert_eq!(title(TOPICS[0]), "Getting started");
+        assert_eq!(search(""), (0..TOPICS.len()).collect::<Vec<_>>());
+        let matches = search("  WCAG ");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(title(TOPICS[matches[0]]), "Appearance and accessibility");
+        assert!(search("no such words").is_empty());
+    }
+
+    #[test]
+    fn every_bundled_font_has_a_license() {
+        for name in crate::fonts::bundled_fonts().keys() {
+            assert!(
+                FONT_LICENSES.iter().any(|(font, _)| font == name),
+                "no license for {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn release_notes_are_for_this_version() {
+        assert!(WHATS_NEW.starts_with(&format!("## {VERSION}")));
+        assert!(WHATS_NEW.lines().count() > 1, "CHANGELOG.md has no notes");
+    }
+}
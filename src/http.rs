@@ -0,0 +1,60 @@
+//! Minimal HTTP GET that works both natively (on a background thread) and on the web (`fetch`).
+
+/// Fetches `url` and calls `on_done` with the response body, or an error message.
+///
+/// `on_done` is called from another thread on native, so remember to request a repaint.
+pub fn get(url: String, on_done: impl 'static + Send + FnOnce(Result<Vec<u8>, String>)) {
+    tracing::debug!("GET {url}");
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread::Builder::new()
+        .name("http".to_owned())
+        .spawn(move || on_done(get_native(&url)))
+        .expect("failed to spawn http thread");
+
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(async move { on_done(get_web(&url).await) });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn get_native(url: &str) -> Result<Vec<u8>, String> {
+    use std::io::Read as _;
+
+    let response = match ureq::get(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(status, response)) => {
+            return Err(format!("{status} {}", response.status_text()));
+        }
+        Err(err) => return Err(err.to_string()),
+    };
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .map_err(|err| err.to_string())?;
+    Ok(bytes)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn get_web(url: &str) -> Result<Vec<u8>, String> {
+    use wasm_bindgen::JsCast as _;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+    let response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(js_error)?;
+    let response: web_sys::Response = response.dyn_into().map_err(js_error)?;
+    if !response.ok() {
+        return Err(format!("{} {}", response.status(), response.status_text()));
+    }
+    let buffer = JsFuture::from(response.array_buffer().map_err(js_error)?)
+        .await
+        .map_err(js_error)?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn js_error(value: wasm_bindgen::JsValue) -> String {
+    value.as_string().unwrap_or_else(|| format!("{value:?}"))
+}
@@ -0,0 +1,595 @@
+//! Translations of the UI, from message catalogs in `locales/` that are built into the
+//! binary.
+//!
+//! The catalogs use a subset of [Fluent](https://projectfluent.org/): messages with
+//! variables, string literals and selectors on plural categories or exact numbers:
+//!
+//! ```ftl
+//! # A comment
+//! greeting = Hello, { $name }!
+//! contract-count = { $count ->
+//!     [0] No contracts
+//!     [one] One contract
+//!    *[other] { $count } contracts
+//! }
+//! ```
+//!
+//! Messages missing from a catalog fall back to English, and so do all messages while the
+//! fonts can't show the language's script. Messages are in logical order; right-to-left
+//! text is reordered when it's laid out, see [`crate::direction`]. The translator of the
+//! current language lives in the egui context:
+//!
+//! ```ignore
+//! let t = Translator::load(ctx);
+//! ui.label(t.format("contract-count", &[("count", 3.into())]));
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::direction::Direction;
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Hebrew,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::Hebrew];
+
+    /// The BCP 47 code, e.g. `en`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Hebrew => "he",
+        }
+    }
+
+    /// The name of the language in that language, for the language menu.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Hebrew => "עברית",
+        }
+    }
+
+    fn catalog_source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+            Language::Hebrew => include_str!("../locales/he.ftl"),
+        }
+    }
+
+    pub fn direction(self) -> Direction {
+        match self {
+            Language::English | Language::German => Direction::LeftToRight,
+            Language::Hebrew => Direction::RightToLeft,
+        }
+    }
+
+    /// Letters the fonts need to show the language.
+    fn sample(self) -> &'static str {
+        match self {
+            Language::English => "a",
+            Language::German => "äöüß",
+            Language::Hebrew => "אבג",
+        }
+    }
+
+    /// The CLDR plural category of `n`, which selectors match against.
+    fn plural_category(self, n: f64) -> &'static str {
+        match self {
+            Language::English | Language::German => {
+                if n == 1.0 {
+                    "one"
+                } else {
+                    "other"
+                }
+            }
+            Language::Hebrew => {
+                if n == 1.0 {
+                    "one"
+                } else if n == 2.0 {
+                    "two"
+                } else {
+                    "other"
+                }
+            }
+        }
+    }
+
+    /// The digit group and decimal separators.
+    fn separators(self) -> (char, char) {
+        match self {
+            Language::English => (',', '.'),
+            Language::German => ('.', ','),
+            Language::Hebrew => (',', '.'),
+        }
+    }
+}
+
+/// A value for a `{ $variable }` in a message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    Text(&'a str),
+    Number(f64),
+}
+
+impl<'a> From<&'a str> for Value<'a> {
+    fn from(text: &'a str) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl<'a> From<&'a String> for Value<'a> {
+    fn from(text: &'a String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<f64> for Value<'_> {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<usize> for Value<'_> {
+    fn from(n: usize) -> Self {
+        Value::Number(n as f64)
+    }
+}
+
+pub struct Translator {
+    language: Language,
+    catalog: Catalog,
+    english: Catalog,
+    /// Messages we already warned about, so the log isn't flooded every frame.
+    missing: Mutex<BTreeSet<String>>,
+    /// Whether the fonts have the letters of the language, see [`Self::check_fonts`].
+    displayable: AtomicBool,
+}
+
+impl Default for Translator {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+impl Translator {
+    pub fn new(language: Language) -> Self {
+        let parse = |language: Language| {
+            Catalog::parse(language.catalog_source()).unwrap_or_else(|err| {
+                tracing::error!("invalid {} catalog: {err}", language.code());
+                Catalog::default()
+            })
+        };
+        Self {
+            language,
+            catalog: parse(language),
+            english: parse(Language::English),
+            missing: Default::default(),
+            displayable: AtomicBool::new(true),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Makes this the translator returned by [`Self::load`].
+    pub fn install(self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(translator_id(), Arc::new(self)));
+    }
+
+    /// The installed translator, or an English one.
+    pub fn load(ctx: &egui::Context) -> Arc<Self> {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_insert_with(translator_id(), || Arc::new(Self::default()))
+                .clone()
+        })
+    }
+
+    /// The language messages are shown in: English while the fonts can't show the chosen one.
+    pub fn displayed_language(&self) -> Language {
+        if self.displayable.load(Ordering::Relaxed) {
+            self.language
+        } else {
+            Language::English
+        }
+    }
+
+    /// Checks that the fonts of `ctx` can show the language, e.g. after loading a font.
+    /// Call this once a frame.
+    pub fn check_fonts(&self, ctx: &egui::Context) {
+        let font_id = egui::FontId::default();
+        let displayable = ctx.fonts(|fonts| fonts.has_glyphs(&font_id, self.language.sample()));
+        if self.displayable.swap(displayable, Ordering::Relaxed) && !displayable {
+            tracing::warn!(
+                "the fonts have no letters for {}, showing English instead",
+                self.language.code()
+            );
+        }
+    }
+
+    /// The message `id`, which doesn't take any arguments.
+    pub fn get(&self, id: &str) -> String {
+        self.format(id, &[])
+    }
+
+    /// The message `id`, with `args` for its variables, in logical order.
+    pub fn format(&self, id: &str, args: &[(&str, Value<'_>)]) -> String {
+        let displayed = self.displayed_language();
+        let catalog = if displayed == self.language {
+            &self.catalog
+        } else {
+            &self.english
+        };
+        let (message, language) = match catalog.get(id) {
+            Some(message) => (message, displayed),
+            None => {
+                self.warn_missing(id);
+                match self.english.get(id) {
+                    Some(message) => (message, Language::English),
+                    None => return id.to_owned(),
+                }
+            }
+        };
+        let mut out = String::new();
+        write_pattern(&mut out, message, args, language);
+        out
+    }
+
+    /// `n` with the digit group and decimal separators of the language.
+    pub fn number(&self, n: f64) -> String {
+        format_number(n, self.language)
+    }
+
+    /// A token amount given in its smallest unit, e.g. uscrt, with `decimals` digits after
+    /// the decimal point of the display unit. Trailing zeros are left out.
+    pub fn amount(&self, amount: u128, decimals: u32) -> String {
+        let (whole, fraction) = match 10u128.checked_pow(decimals) {
+            Some(unit) => (amount / unit, amount % unit),
+            // More decimals than a u128 has digits: all of it is the fraction.
+            None => (0, amount),
+        };
+        let (group, decimal) = self.language.separators();
+        let mut out = group_digits(&whole.to_string(), group);
+        let fraction = format!("{fraction:0width$}", width = decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if !fraction.is_empty() {
+            out.push(decimal);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    fn warn_missing(&self, id: &str) {
+        if self.missing.lock().unwrap().insert(id.to_owned()) {
+            tracing::warn!("no message {id:?} in locales/{}.ftl", self.language.code());
+        }
+    }
+}
+
+fn translator_id() -> egui::Id {
+    egui::Id::new("translator")
+}
+
+fn format_number(n: f64, language: Language) -> String {
+    let (group, decimal) = language.separators();
+    let text = n.abs().to_string();
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    out.push_str(&group_digits(integer, group));
+    if !fraction.is_empty() {
+        out.push(decimal);
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// `1234567` -> `1,234,567`
+fn group_digits(digits: &str, separator: char) -> String {
+    let mut out = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(separator);
+        }
+        out.push(digit);
+    }
+    out
+}
+
+// ----------------------------------------------------------------------------
+// Catalogs
+
+type Pattern = Vec<Element>;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Element {
+    Text(String),
+    Variable(String),
+    Select {
+        variable: String,
+        variants: Vec<(VariantKey, Pattern)>,
+        /// Index of the `*[default]` variant.
+        default: usize,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum VariantKey {
+    Number(f64),
+    Name(String),
+}
+
+#[derive(Default)]
+struct Catalog {
+    messages: HashMap<String, Pattern>,
+}
+
+impl Catalog {
+    fn parse(source: &str) -> Result<Self, String> {
+        let mut messages = HashMap::new();
+        let mut lines = source.lines().enumerate().peekable();
+        while let Some((number, line)) = lines.next() {
+            let line_error = |err: String| format!("line {}: {err}", number + 1);
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (id, value) = line
+                .split_once('=')
+                .ok_or_else(|| line_error("expected `id = value`".to_owned()))?;
+            let id = id.trim();
+            if id.is_empty() || !id.chars().all(|c| c.is_alphanumeric() || c == '-') {
+                return Err(line_error(format!("invalid message id {id:?}")));
+            }
+            // Indented lines continue the message, and so does the `}` closing a selector.
+            let mut value = value.trim().to_owned();
+            while let Some((_, next)) =
+                lines.next_if(|(_, next)| next.starts_with(' ') || next.starts_with('}'))
+            {
+                value.push('\n');
+                value.push_str(next.trim());
+            }
+            let pattern = Parser::new(&value).pattern(false).map_err(line_error)?;
+            if messages.insert(id.to_owned(), pattern).is_some() {
+                return Err(line_error(format!("{id} is defined twice")));
+            }
+        }
+        Ok(Self { messages })
+    }
+
+    fn get(&self, id: &str) -> Option<&Pattern> {
+        self.messages.get(id)
+    }
+}
+
+struct Parser<'s> {
+    rest: &'s str,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Self {
+        Self { rest: source }
+    }
+
+    /// Text and placeables, up to the end of the line if `in_variant`.
+    fn pattern(&mut self, in_variant: bool) -> Result<Pattern, String> {
+        let mut pattern = Vec::new();
+        loop {
+            let end = self
+                .rest
+                .find(|c| c == '{' || (in_variant && c == '\n'))
+                .unwrap_or(self.rest.len());
+            if end > 0 {
+                pattern.push(Element::Text(self.rest[..end].to_owned()));
+            }
+            self.rest = &self.rest[end..];
+            if !self.rest.starts_with('{') {
+                return Ok(pattern);
+            }
+            self.rest = &self.rest[1..];
+            pattern.push(self.placeable()?);
+        }
+    }
+
+    /// What follows a `{`.
+    fn placeable(&mut self) -> Result<Element, String> {
+        self.skip_whitespace();
+        if let Some(rest) = self.rest.strip_prefix('"') {
+            let end = rest.find('"').ok_or("unterminated string literal")?;
+            let text = rest[..end].to_owned();
+            self.rest = &rest[end + 1..];
+            self.expect('}')?;
+            return Ok(Element::Text(text));
+        }
+        let variable = self.variable()?;
+        self.skip_whitespace();
+        if self.rest.starts_with('}') {
+            self.rest = &self.rest[1..];
+            return Ok(Element::Variable(variable));
+        }
+        self.rest = self
+            .rest
+            .strip_prefix("->")
+            .ok_or_else(|| format!("expected `}}` or `->` after ${variable}"))?;
+
+        let mut variants = Vec::new();
+        let mut default = None;
+        loop {
+            self.skip_whitespace();
+            if let Some(rest) = self.rest.strip_prefix('}') {
+                self.rest = rest;
+                break;
+            }
+            if let Some(rest) = self.rest.strip_prefix('*') {
+                self.rest = rest;
+                if default.replace(variants.len()).is_some() {
+                    return Err("more than one default variant".to_owned());
+                }
+            }
+            let rest = self.rest.strip_prefix('[').ok_or("expected a variant")?;
+            let end = rest.find(']').ok_or("unterminated variant key")?;
+            let key = rest[..end].trim();
+            let key = match key.parse() {
+                Ok(n) => VariantKey::Number(n),
+                Err(_) => VariantKey::Name(key.to_owned()),
+            };
+            self.rest = rest[end + 1..].trim_start_matches(' ');
+            variants.push((key, self.pattern(true)?));
+        }
+        let default =
+            default.ok_or_else(|| format!("the selector on ${variable} has no default"))?;
+        Ok(Element::Select {
+            variable,
+            variants,
+            default,
+        })
+    }
+
+    fn variable(&mut self) -> Result<String, String> {
+        let rest = self.rest.strip_prefix('$').ok_or("expected a $variable")?;
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        self.rest = &rest[end..];
+        Ok(rest[..end].to_owned())
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        self.skip_whitespace();
+        self.rest = self
+            .rest
+            .strip_prefix(c)
+            .ok_or_else(|| format!("expected `{c}`"))?;
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+}
+
+fn write_pattern(
+    out: &mut String,
+    pattern: &Pattern,
+    args: &[(&str, Value<'_>)],
+    language: Language,
+) {
+    let arg = |name: &str| {
+        args.iter()
+            .find(|(arg, _)| *arg == name)
+            .map(|(_, value)| value)
+    };
+    for element in pattern {
+        match element {
+            Element::Text(text) => out.push_str(text),
+            Element::Variable(name) => match arg(name) {
+                Some(Value::Text(text)) => out.push_str(text),
+                Some(Value::Number(n)) => out.push_str(&format_number(*n, language)),
+                None => {
+                    out.push('$');
+                    out.push_str(name);
+                }
+            },
+            Element::Select {
+                variable,
+                variants,
+                default,
+            } => {
+                let matches = |key: &VariantKey| match (key, arg(variable)) {
+                    (VariantKey::Number(key), Some(Value::Number(n))) => key == n,
+                    (VariantKey::Name(key), Some(Value::Number(n))) => {
+                        key == language.plural_category(*n)
+                    }
+                    (VariantKey::Name(key), Some(Value::Text(text))) => key == text,
+                    _ => false,
+                };
+                // Exact numbers win over plural categories, like in Fluent.
+                let variant = variants
+                    .iter()
+                    .position(|(key, _)| matches!(key, VariantKey::Number(_)) && matches(key))
+                    .or_else(|| variants.iter().position(|(key, _)| matches(key)))
+                    .unwrap_or(*default);
+                write_pattern(out, &variants[variant].1, args, language);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogs_have_every_english_message() {
+        let english = Catalog::parse(Language::English.catalog_source()).unwrap();
+        for language in Language::ALL {
+            let catalog = Catalog::parse(language.catalog_source())
+                .unwrap_or_else(|err| panic!("{}.ftl: {err}", language.code()));
+            let mut missing: Vec<_> = english
+                .messages
+                .keys()
+                .filter(|id| !catalog.messages.contains_key(*id))
+                .collect();
+            missing.sort();
+            assert!(
+                missing.is_empty(),
+                "locales/{}.ftl is missing {missing:?}",
+                language.code()
+            );
+        }
+    }
+
+    #[test]
+    fn plurals_and_numbers() {
+        let source =
+            "count = { $n ->\n    [0] none\n    [one] one item\n   *[other] { $n } items\n}";
+        let translator = Translator {
+            language: Language::German,
+            catalog: Catalog::parse(source).unwrap(),
+            english: Catalog::default(),
+            missing: Default::default(),
+            displayable: AtomicBool::new(true),
+        };
+        let count = |n: f64| translator.format("count", &[("n", n.into())]);
+        assert_eq!(count(0.0), "none");
+        assert_eq!(count(1.0), "one item");
+        assert_eq!(count(1234.5), "1.234,5 items");
+
+        assert_eq!(translator.amount(1_234_500_000, 6), "1.234,5");
+        assert_eq!(translator.amount(42, 6), "0,000042");
+        assert_eq!(Translator::new(Language::English).amount(7_000_000, 6), "7");
+        assert_eq!(
+            translator.amount(u128::MAX, 39),
+            "0,340282366920938463463374607431768211455"
+        );
+        assert_eq!(translator.amount(42, 40), format!("0,{}42", "0".repeat(38)));
+    }
+
+    #[test]
+    fn missing_messages_fall_back_to_english() {
+        let translator = Translator {
+            language: Language::German,
+            catalog: Catalog::parse("spacing = Abstand:{\" \"}").unwrap(),
+            english: Catalog::parse("spacing = Spacing\nquit = Quit").unwrap(),
+            missing: Default::default(),
+            displayable: AtomicBool::new(true),
+        };
+        assert_eq!(translator.get("spacing"), "Abstand: ");
+        assert_eq!(translator.get("quit"), "Quit");
+        assert_eq!(translator.get("nope"), "nope");
+    }
+}
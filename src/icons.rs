@@ -0,0 +1,294 @@
+//! The icons of the app, and buttons and menu items showing them.
+//!
+//! Icons are monochrome SVGs in `assets/icons/`, loaded through [`Assets`], so they are
+//! rasterized for the current scale and painted in the text color of the widget they are in.
+
+use egui::{
+    pos2, vec2, Color32, Rect, Response, Sense, TextStyle, Ui, Widget, WidgetInfo, WidgetText,
+    WidgetType,
+};
+
+use crate::assets::Assets;
+use crate::direction::Direction;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Icon {
+    Accessibility,
+    Camera,
+    Close,
+    Contract,
+    External,
+    Flag,
+    Font,
+    Help,
+    Info,
+    Keyboard,
+    Language,
+    Link,
+    Menu,
+    Palette,
+    PanelLeft,
+    PanelRight,
+    Quit,
+    Sparkle,
+    Terminal,
+}
+
+impl Icon {
+    pub const ALL: [Icon; 19] = [
+        Icon::Accessibility,
+        Icon::Camera,
+        Icon::Close,
+        Icon::Contract,
+        Icon::External,
+        Icon::Flag,
+        Icon::Font,
+        Icon::Help,
+        Icon::Info,
+        Icon::Keyboard,
+        Icon::Language,
+        Icon::Link,
+        Icon::Menu,
+        Icon::Palette,
+        Icon::PanelLeft,
+        Icon::PanelRight,
+        Icon::Quit,
+        Icon::Sparkle,
+        Icon::Terminal,
+    ];
+
+    /// The name of its file in `assets/icons/`, without `.svg`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Icon::Accessibility => "accessibility",
+            Icon::Camera => "camera",
+            Icon::Close => "close",
+            Icon::Contract => "contract",
+            Icon::External => "external",
+            Icon::Flag => "flag",
+            Icon::Font => "font",
+            Icon::Help => "help",
+            Icon::Info => "info",
+            Icon::Keyboard => "keyboard",
+            Icon::Language => "language",
+            Icon::Link => "link",
+            Icon::Menu => "menu",
+            Icon::Palette => "palette",
+            Icon::PanelLeft => "panel_left",
+            Icon::PanelRight => "panel_right",
+            Icon::Quit => "quit",
+            Icon::Sparkle => "sparkle",
+            Icon::Terminal => "terminal",
+        }
+    }
+
+    pub(crate) fn svg(self) -> &'static [u8] {
+        match self {
+            Icon::Accessibility => include_bytes!("../assets/icons/accessibility.svg"),
+            Icon::Camera => include_bytes!("../assets/icons/camera.svg"),
+            Icon::Close => include_bytes!("../assets/icons/close.svg"),
+            Icon::Contract => include_bytes!("../assets/icons/contract.svg"),
+            Icon::External => include_bytes!("../assets/icons/external.svg"),
+            Icon::Flag => include_bytes!("../assets/icons/flag.svg"),
+            Icon::Font => include_bytes!("../assets/icons/font.svg"),
+            Icon::Help => include_bytes!("../assets/icons/help.svg"),
+            Icon::Info => include_bytes!("../assets/icons/info.svg"),
+            Icon::Keyboard => include_bytes!("../assets/icons/keyboard.svg"),
+            Icon::Language => include_bytes!("../assets/icons/language.svg"),
+            Icon::Link => include_bytes!("../assets/icons/link.svg"),
+            Icon::Menu => include_bytes!("../assets/icons/menu.svg"),
+            Icon::Palette => include_bytes!("../assets/icons/palette.svg"),
+            Icon::PanelLeft => include_bytes!("../assets/icons/panel_left.svg"),
+            Icon::PanelRight => include_bytes!("../assets/icons/panel_right.svg"),
+            Icon::Quit => include_bytes!("../assets/icons/quit.svg"),
+            Icon::Sparkle => include_bytes!("../assets/icons/sparkle.svg"),
+            Icon::Terminal => include_bytes!("../assets/icons/terminal.svg"),
+        }
+    }
+
+    /// Its id in [`Assets`].
+    pub fn asset_id(self) -> String {
+        format!("icon/{}", self.name())
+    }
+
+    /// Paints the icon into `rect`, which should be square, in `color`.
+    pub fn paint(self, ui: &Ui, rect: Rect, color: Color32) {
+        let texture =
+            Assets::load(ui.ctx()).texture(ui.ctx(), &self.asset_id(), rect.height(), Some(color));
+        // Errors are reported by the assets.
+        if let Ok(texture) = texture {
+            let painter = ui.painter();
+            // Whole pixels, so the rasterized icon isn't resampled.
+            let rect = Rect::from_min_size(painter.round_pos_to_pixels(rect.min), rect.size());
+            let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+            painter.image(texture.id(), rect, uv, Color32::WHITE);
+        }
+    }
+}
+
+/// A button with an icon, and optionally a label after it. In menus, use it with a label
+/// for an icon and label menu item.
+///
+/// ```ignore
+/// if ui.add(IconButton::new(Icon::Camera).text("Save Screenshot…")).clicked() { … }
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct IconButton {
+    icon: Icon,
+    text: Option<WidgetText>,
+    selected: bool,
+    frame: Option<bool>,
+}
+
+impl IconButton {
+    pub fn new(icon: Icon) -> Self {
+        Self {
+            icon,
+            text: None,
+            selected: false,
+            frame: None,
+        }
+    }
+
+    pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Shown like a selected toggle.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Whether to paint the background, by default if the style's `button_frame` is set.
+    pub fn frame(mut self, frame: bool) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+}
+
+impl Widget for IconButton {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            icon,
+            text,
+            selected,
+            frame,
+        } = self;
+        let frame = frame.unwrap_or_else(|| ui.visuals().button_frame);
+        let padding = ui.spacing().button_padding;
+        let icon_size = ui.text_style_height(&TextStyle::Button).round();
+        let icon_spacing = ui.spacing().icon_spacing;
+
+        let text = text.map(|text| {
+            let wrap_width = ui.available_width() - 2.0 * padding.x - icon_size - icon_spacing;
+            text.into_galley(ui, Some(false), wrap_width, TextStyle::Button)
+        });
+        let mut desired_size = vec2(icon_size, icon_size);
+        if let Some(text) = &text {
+            desired_size.x += icon_spacing + text.size().x;
+            desired_size.y = desired_size.y.max(text.size().y);
+        }
+        desired_size += 2.0 * padding;
+        desired_size.y = desired_size.y.max(ui.spacing().interact_size.y);
+
+        let (rect, response) = ui.allocate_at_least(desired_size, Sense::click());
+        response.widget_info(|| match &text {
+            Some(text) => WidgetInfo::labeled(WidgetType::Button, text.text()),
+            None => WidgetInfo::labeled(WidgetType::Button, icon.name()),
+        });
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact_selectable(&response, selected);
+            if frame || selected {
+                ui.painter().rect(
+                    rect.expand(visuals.expansion),
+                    visuals.rounding,
+                    visuals.weak_bg_fill,
+                    visuals.bg_stroke,
+                );
+            }
+
+            // The icon leads: on the left, or on the right in right-to-left layouts.
+            let rtl = Direction::load(ui.ctx()).is_rtl();
+            let icon_x = match &text {
+                Some(_) if rtl => rect.max.x - padding.x - icon_size,
+                Some(_) => rect.min.x + padding.x,
+                None => rect.center().x - icon_size / 2.0,
+            };
+            let icon_rect = Rect::from_min_size(
+                pos2(icon_x, rect.center().y - icon_size / 2.0),
+                vec2(icon_size, icon_size),
+            );
+            icon.paint(ui, icon_rect, visuals.fg_stroke.color);
+
+            if let Some(text) = text {
+                let text_x = if rtl {
+                    icon_rect.min.x - icon_spacing - text.size().x
+                } else {
+                    icon_rect.max.x + icon_spacing
+                };
+                let text_pos = pos2(text_x, rect.center().y - text.size().y / 2.0);
+                text.paint_with_visuals(ui.painter(), text_pos, &visuals);
+            }
+        }
+        response
+    }
+}
+
+/// Lists every icon at a few sizes, with its name. For checking new icons.
+pub fn debug_ui(ui: &mut Ui) {
+    let assets = Assets::load(ui.ctx());
+    let color = ui.visuals().text_color();
+    egui::Grid::new("icons").striped(true).show(ui, |ui| {
+        for icon in Icon::ALL {
+            for size in [16.0, 24.0, 32.0] {
+                assets.image(ui, &icon.asset_id(), size, Some(color));
+            }
+            ui.label(icon.name());
+            ui.add(IconButton::new(icon));
+            ui.end_row();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_icon_loads() {
+        let ctx = egui::Context::default();
+        let assets = Assets::load(&ctx);
+        let _ = ctx.run(Default::default(), |ctx| {
+            for icon in Icon::ALL {
+                let texture = assets.texture(ctx, &icon.asset_id(), 24.0, None);
+                assert_eq!(texture.map(|t| t.size()), Ok([24, 24]), "{icon:?}");
+            }
+        });
+    }
+
+    #[test]
+    fn icon_buttons_mirror_in_right_to_left_layouts() {
+        for direction in [Direction::LeftToRight, Direction::RightToLeft] {
+            let ctx = egui::Context::default();
+            direction.store(&ctx);
+            let output = ctx.run(Default::default(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.add(IconButton::new(Icon::Camera).text("Save"));
+                });
+            });
+            let (mut icon_x, mut text_x) = (None, None);
+            for epaint::ClippedShape(_, shape) in &output.shapes {
+                match shape {
+                    egui::Shape::Mesh(mesh) => icon_x = Some(mesh.calc_bounds().center().x),
+                    egui::Shape::Text(text) => text_x = Some(text.pos.x),
+                    _ => {}
+                }
+            }
+            let (icon_x, text_x) = (icon_x.unwrap(), text_x.unwrap());
+            assert_eq!(icon_x > text_x, direction.is_rtl(), "{direction:?}");
+        }
+    }
+}
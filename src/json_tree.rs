@@ -0,0 +1,517 @@
+//! A collapsible, searchable viewer for JSON documents.
+//!
+//! ```ignore
+//! JsonTree::new("response", &value).searchable(true).show(ui);
+//! ```
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use egui::text::LayoutJob;
+use egui::{
+    CollapsingHeader, FontId, Id, Label, Response, RichText, Sense, TextEdit, TextFormat,
+    TextStyle, Ui,
+};
+use serde_json::Value;
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+use crate::style::{my_syntax_colors, SyntaxColors};
+
+/// Containers with more children than this are split into collapsible ranges,
+/// so opening a huge array only lays out one page at a time.
+const PAGE_SIZE: usize = 100;
+
+#[must_use = "You should call .show()"]
+pub struct JsonTree<'a> {
+    id_source: Id,
+    value: &'a Value,
+    root_name: String,
+    searchable: bool,
+    default_open: bool,
+    generation: u64,
+}
+
+impl<'a> JsonTree<'a> {
+    pub fn new(id_source: impl Hash, value: &'a Value) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            value,
+            root_name: String::new(),
+            searchable: false,
+            default_open: true,
+            generation: 0,
+        }
+    }
+
+    /// Label shown on the root node. Empty by default.
+    pub fn root_name(mut self, root_name: impl Into<String>) -> Self {
+        self.root_name = root_name.into();
+        self
+    }
+
+    /// Show a search box above the tree. Only matching nodes (and their parents) are shown
+    /// while searching.
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
+        self
+    }
+
+    /// Whether the root node starts out expanded. Default: `true`.
+    pub fn default_open(mut self, default_open: bool) -> Self {
+        self.default_open = default_open;
+        self
+    }
+
+    /// Changes when the document does. The search is cached until the query or this
+    /// changes, so bump it when showing a different document with the same id. Default: 0.
+    pub fn generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let id = ui.make_persistent_id(self.id_source);
+        let t = Translator::load(ui.ctx());
+        ui.vertical(|ui| {
+            let search = if self.searchable {
+                let mut query = ui
+                    .data_mut(|d| d.get_temp::<String>(id.with("query")))
+                    .unwrap_or_default();
+                let search = ui
+                    .horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut query)
+                                .hint_text(visual_order(&t.get("json-search")))
+                                .desired_width(200.0),
+                        );
+                        let search = Search::cached(ui, id, self.value, &query, self.generation);
+                        if let Some(search) = &search {
+                            let count = search.hits.len();
+                            ui.add(BidiLabel::new(
+                                RichText::new(t.format("json-matches", &[("count", count.into())]))
+                                    .weak(),
+                            ));
+                        }
+                        search
+                    })
+                    .inner;
+                ui.data_mut(|d| d.insert_temp(id.with("query"), query));
+                search
+            } else {
+                None
+            };
+
+            let viewer = Viewer {
+                id,
+                colors: my_syntax_colors(ui.visuals()),
+                font_id: TextStyle::Monospace.resolve(ui.style()),
+                search: search.as_deref(),
+                default_open: self.default_open,
+                t: &t,
+            };
+            viewer.node(ui, &Key::Root(&self.root_name), self.value, "$");
+        })
+        .response
+    }
+}
+
+/// The result of searching a document, cached until the query or document changes.
+struct Search {
+    query: String,
+    /// The [`JsonTree::generation`] of the document.
+    generation: u64,
+    /// Nodes whose key or value contains the query.
+    hits: HashSet<String>,
+    /// Hits, plus their ancestors and everything below a matching container.
+    visible: HashSet<String>,
+    /// Containers with a hit somewhere below them.
+    expanded: HashSet<String>,
+}
+
+impl Search {
+    fn cached(ui: &Ui, id: Id, value: &Value, query: &str, generation: u64) -> Option<Arc<Self>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return None;
+        }
+        let cache_id = id.with("search");
+        if let Some(cached) = ui.data_mut(|d| d.get_temp::<Arc<Search>>(cache_id)) {
+            if cached.query == query && cached.generation == generation {
+                return Some(cached);
+            }
+        }
+        let search = Arc::new(Self::new(value, query, generation));
+        ui.data_mut(|d| d.insert_temp(cache_id, search.clone()));
+        Some(search)
+    }
+
+    /// Searches `value` for `query`, which must be lowercase.
+    fn new(value: &Value, query: String, generation: u64) -> Self {
+        let mut search = Search {
+            query,
+            generation,
+            hits: Default::default(),
+            visible: Default::default(),
+            expanded: Default::default(),
+        };
+        search.visit("", value, "$", false);
+        search
+    }
+
+    /// Returns `true` if the node at `path` contains a hit, itself included.
+    fn visit(&mut self, key: &str, value: &Value, path: &str, inside_hit: bool) -> bool {
+        let key_hit = key.to_lowercase().contains(&self.query);
+        let hit = match value {
+            Value::Object(_) | Value::Array(_) => key_hit,
+            leaf => key_hit || leaf_text(leaf).to_lowercase().contains(&self.query),
+        };
+        if hit {
+            self.hits.insert(path.to_owned());
+        }
+
+        let mut hit_below = false;
+        for_each_child(value, path, |child_key, child, child_path| {
+            hit_below |= self.visit(child_key, child, child_path, inside_hit || hit);
+        });
+        if hit_below {
+            self.expanded.insert(path.to_owned());
+        }
+        if hit || hit_below || inside_hit {
+            self.visible.insert(path.to_owned());
+        }
+        hit || hit_below
+    }
+}
+
+fn for_each_child(value: &Value, path: &str, mut visit: impl FnMut(&str, &Value, &str)) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                visit(key, child, &child_path(path, &Key::Name(key)));
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                // Array indices aren't worth matching against.
+                visit("", child, &child_path(path, &Key::Index(index)));
+            }
+        }
+        _ => {}
+    }
+}
+
+enum Key<'a> {
+    Root(&'a str),
+    Name(&'a str),
+    Index(usize),
+}
+
+/// A JSONPath-style path, e.g. `$.balances[3].amount`.
+fn child_path(parent: &str, key: &Key<'_>) -> String {
+    match key {
+        Key::Root(_) => parent.to_owned(),
+        Key::Name(name) => {
+            let is_identifier = !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if is_identifier {
+                format!("{parent}.{name}")
+            } else {
+                format!("{parent}[{}]", Value::String((*name).to_owned()))
+            }
+        }
+        Key::Index(index) => format!("{parent}[{index}]"),
+    }
+}
+
+fn leaf_text(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        other => other.to_string(),
+    }
+}
+
+struct Viewer<'a> {
+    id: Id,
+    colors: SyntaxColors,
+    font_id: FontId,
+    search: Option<&'a Search>,
+    default_open: bool,
+    t: &'a Translator,
+}
+
+impl Viewer<'_> {
+    fn node(&self, ui: &mut Ui, key: &Key<'_>, value: &Value, path: &str) {
+        if let Some(search) = self.search {
+            if !search.visible.contains(path) {
+                return;
+            }
+        }
+        match value {
+            Value::Object(map) => {
+                let keys = self.t.format("json-keys", &[("count", map.len().into())]);
+                let summary = format!("{{…}} {keys}");
+                self.container(ui, key, value, path, &summary, |ui| {
+                    let children: Vec<_> = map.iter().collect();
+                    let path_of = |index: usize| child_path(path, &Key::Name(children[index].0));
+                    self.children(ui, 0, children.len(), &path_of, &|ui, index| {
+                        let (child_key, child) = children[index];
+                        let child_key = Key::Name(child_key);
+                        self.node(ui, &child_key, child, &child_path(path, &child_key));
+                    });
+                });
+            }
+            Value::Array(items) => {
+                let items_text = self
+                    .t
+                    .format("json-items", &[("count", items.len().into())]);
+                let summary = format!("[…] {items_text}");
+                self.container(ui, key, value, path, &summary, |ui| {
+                    let path_of = |index: usize| child_path(path, &Key::Index(index));
+                    self.children(ui, 0, items.len(), &path_of, &|ui, index| {
+                        let child_key = Key::Index(index);
+                        self.node(ui, &child_key, &items[index], &child_path(path, &child_key));
+                    });
+                });
+            }
+            leaf => self.leaf(ui, key, leaf, path),
+        }
+    }
+
+    fn container(
+        &self,
+        ui: &mut Ui,
+        key: &Key<'_>,
+        value: &Value,
+        path: &str,
+        summary: &str,
+        add_body: impl FnOnce(&mut Ui),
+    ) {
+        let mut job = LayoutJob::default();
+        self.append_key(&mut job, key, path);
+        job.append(
+            &visual_order(summary),
+            0.0,
+            self.format(self.colors.punctuation, path),
+        );
+
+        let mut header = CollapsingHeader::new(job)
+            .id_source(self.id.with(path))
+            .default_open(matches!(key, Key::Root(_)) && self.default_open);
+        if let Some(search) = self.search {
+            if search.expanded.contains(path) {
+                header = header.open(Some(true));
+            }
+        }
+        let response = header.show(ui, add_body);
+        self.context_menu(response.header_response, value, path);
+    }
+
+    /// Shows children `start..end`, grouping them into collapsible ranges when there are many.
+    /// While searching, ranges without anything to show are left out and those with hits
+    /// are opened.
+    fn children(
+        &self,
+        ui: &mut Ui,
+        start: usize,
+        end: usize,
+        path_of: &dyn Fn(usize) -> String,
+        add_child: &dyn Fn(&mut Ui, usize),
+    ) {
+        let len = end - start;
+        if len <= PAGE_SIZE {
+            for index in start..end {
+                add_child(ui, index);
+            }
+            return;
+        }
+        let mut span = PAGE_SIZE;
+        while (len + span - 1) / span > PAGE_SIZE {
+            span *= PAGE_SIZE;
+        }
+        for range_start in (start..end).step_by(span) {
+            let range_end = (range_start + span).min(end);
+            let mut open = None;
+            if let Some(search) = self.search {
+                let paths = (range_start..range_end).map(path_of);
+                let (mut visible, mut hit) = (false, false);
+                for path in paths {
+                    visible |= search.visible.contains(&path);
+                    hit |= search.hits.contains(&path) || search.expanded.contains(&path);
+                    if hit {
+                        break;
+                    }
+                }
+                if !visible {
+                    continue;
+                }
+                open = hit.then_some(true);
+            }
+            let label = format!("[{range_start} … {}]", range_end - 1);
+            CollapsingHeader::new(label)
+                .id_source(ui.id().with(range_start))
+                .open(open)
+                .show(ui, |ui| {
+                    self.children(ui, range_start, range_end, path_of, add_child)
+                });
+        }
+    }
+
+    fn leaf(&self, ui: &mut Ui, key: &Key<'_>, value: &Value, path: &str) {
+        let color = match value {
+            Value::String(_) => self.colors.string,
+            Value::Number(_) => self.colors.number,
+            _ => self.colors.literal,
+        };
+        let mut job = LayoutJob::default();
+        self.append_key(&mut job, key, path);
+        job.append(&value.to_string(), 0.0, self.format(color, path));
+        let response = ui.add(Label::new(job).sense(Sense::click()));
+        self.context_menu(response, value, path);
+    }
+
+    fn append_key(&self, job: &mut LayoutJob, key: &Key<'_>, path: &str) {
+        let key = match key {
+            Key::Root("") => return,
+            Key::Root(name) | Key::Name(name) => (*name).to_owned(),
+            Key::Index(index) => index.to_string(),
+        };
+        job.append(&key, 0.0, self.format(self.colors.key, path));
+        job.append(": ", 0.0, self.format(self.colors.punctuation, ""));
+    }
+
+    fn format(&self, color: egui::Color32, path: &str) -> TextFormat {
+        let is_hit = self
+            .search
+            .map_or(false, |search| search.hits.contains(path));
+        TextFormat {
+            font_id: self.font_id.clone(),
+            color,
+            background: if is_hit {
+                color.linear_multiply(0.25)
+            } else {
+                Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn context_menu(&self, response: Response, value: &Value, path: &str) {
+        response.context_menu(|ui| {
+            if ui
+                .button(visual_order(&self.t.get("json-copy-path")))
+                .clicked()
+            {
+                ui.output_mut(|o| o.copied_text = path.to_owned());
+                ui.close_menu();
+            }
+            if ui
+                .button(visual_order(&self.t.get("json-copy-value")))
+                .clicked()
+            {
+                let text = match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        serde_json::to_string_pretty(value).unwrap_or_default()
+                    }
+                    leaf => leaf_text(leaf),
+                };
+                ui.output_mut(|o| o.copied_text = text);
+                ui.close_menu();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn search_shows_hits_with_their_parents_expanded() {
+        let value = json!({
+            "admin": "secret1admin",
+            "balances": [
+                { "denom": "uscrt", "amount": "10" },
+                { "denom": "uatom", "amount": "2" },
+            ],
+            "config": { "uscrt_fee": { "amount": "1" } },
+        });
+        let search = Search::new(&value, "uscrt".to_owned(), 0);
+        let paths = |set: &HashSet<String>| {
+            let mut paths: Vec<_> = set.iter().cloned().collect();
+            paths.sort();
+            paths
+        };
+        assert_eq!(
+            paths(&search.hits),
+            ["$.balances[0].denom", "$.config.uscrt_fee"]
+        );
+        assert_eq!(
+            paths(&search.expanded),
+            ["$", "$.balances", "$.balances[0]", "$.config"]
+        );
+        // Everything below a matching container is shown, but not the other entries.
+        assert!(search.visible.contains("$.config.uscrt_fee.amount"));
+        assert!(!search.visible.contains("$.balances[0].amount"));
+        assert!(!search.visible.contains("$.balances[1]"));
+        assert!(!search.visible.contains("$.admin"));
+    }
+
+    #[test]
+    fn search_is_cached_per_generation() {
+        let ctx = egui::Context::default();
+        let mut value = json!({ "label": "first" });
+        let mut hits = Vec::new();
+        for (label, generation) in [("first", 0), ("second", 0), ("second", 1)] {
+            // The same local, like a document cloned every frame.
+            value["label"] = json!(label);
+            let _ = ctx.run(Default::default(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let search =
+                        Search::cached(ui, Id::new("tree"), &value, "FIRST", generation).unwrap();
+                    hits.push(search.hits.len());
+                });
+            });
+        }
+        assert_eq!(hits, [1, 1, 0]);
+    }
+
+    #[test]
+    fn search_opens_the_ranges_with_hits_and_hides_the_others() {
+        let items: Vec<_> = (0..250).map(|i| json!(format!("item {i}"))).collect();
+        let value = json!({ "items": items });
+        let ctx = egui::Context::default();
+        let mut texts = Vec::new();
+        for frame in 0..5 {
+            let input = egui::RawInput {
+                time: Some(frame as f64),
+                ..Default::default()
+            };
+            let output = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        let id = ui.make_persistent_id(Id::new("tree")).with("query");
+                        ui.data_mut(|d| d.insert_temp(id, "item 142".to_owned()));
+                        JsonTree::new("tree", &value).searchable(true).show(ui);
+                    });
+                });
+            });
+            texts = output
+                .shapes
+                .iter()
+                .filter_map(|clipped| match &clipped.1 {
+                    egui::Shape::Text(text) => Some(text.galley.text().to_owned()),
+                    _ => None,
+                })
+                .collect();
+        }
+        assert!(texts.iter().any(|text| text == "[100 … 199]"));
+        assert!(texts.iter().any(|text| text.contains("\"item 142\"")));
+        assert!(!texts.iter().any(|text| text == "[0 … 99]"));
+        assert!(!texts.iter().any(|text| text == "[200 … 249]"));
+    }
+}
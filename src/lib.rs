@@ -1,5 +1,35 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+pub mod accessibility;
 mod app;
+pub mod assets;
+pub mod background;
+mod contracts;
+pub mod contrast;
+pub mod direction;
+mod files;
+pub mod fonts;
+#[cfg(test)]
+mod harness;
+mod help;
+mod http;
+pub mod i18n;
+pub mod icons;
+pub mod json_tree;
+pub mod links;
+pub mod logs;
+pub mod markdown;
+pub mod network;
+pub mod notifications;
+mod pwa;
+pub mod raster;
+pub mod router;
+mod screenshot;
+pub mod storage;
 pub mod style;
-pub use app::TemplateApp;
+pub mod text_styles;
+mod tour;
+pub use app::{AppOptions, TemplateApp};
+
+/// Used by eframe to pick where to save app state on native, and by [`storage::Storage`].
+pub const APP_NAME: &str = "eframe native app";
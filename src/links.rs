@@ -0,0 +1,655 @@
+//! Links to other sites, and which of them the app opens.
+//!
+//! Every link goes through [`Links::open`], which checks it against the [`LinkPolicy`]:
+//! links to trusted domains open right away, links to other web sites and email addresses
+//! only after the user confirms them in a dialog showing the full URL, and anything else
+//! (`javascript:`, `data:`, `file:`, blocked domains, ...) never opens.
+//!
+//! ```ignore
+//! ui.add(CustomHyperlink::from_label_and_url("GitHub", "https://github.com/").external_icon(true));
+//! ```
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use egui::output::OpenUrl;
+use egui::{
+    epaint, vec2, Align2, Color32, CursorIcon, Grid, Label, Response, RichText, Sense, Stroke,
+    TextEdit, TextStyle, Ui, Widget, WidgetInfo, WidgetText, WidgetType, Window,
+};
+use url::Url;
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+use crate::icons::Icon;
+
+/// Where the links in the bottom bar go, and the policy for all links. Saved with the app.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct LinkSettings {
+    /// Left out of the bottom bar if empty.
+    pub discord: String,
+    pub twitter: String,
+    pub github: String,
+    pub policy: LinkPolicy,
+}
+
+impl Default for LinkSettings {
+    fn default() -> Self {
+        Self {
+            discord: String::new(),
+            twitter: String::new(),
+            github: "https://github.com/kent-3/egui-experiments".to_owned(),
+            policy: LinkPolicy::default(),
+        }
+    }
+}
+
+impl LinkSettings {
+    /// The links of the bottom bar that are set, by name.
+    pub fn social(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("Discord", self.discord.as_str()),
+            ("Twitter", self.twitter.as_str()),
+            ("GitHub", self.github.as_str()),
+        ]
+        .into_iter()
+        .filter(|(_, url)| !url.trim().is_empty())
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, t: &Translator) {
+        Grid::new("social_links").num_columns(2).show(ui, |ui| {
+            for (name, url) in [
+                ("Discord", &mut self.discord),
+                ("Twitter", &mut self.twitter),
+                ("GitHub", &mut self.github),
+            ] {
+                ui.label(name);
+                ui.add(TextEdit::singleline(url).desired_width(280.0));
+                ui.end_row();
+            }
+        });
+        ui.add(BidiLabel::new(
+            RichText::new(t.get("links-social-hint")).weak(),
+        ));
+        ui.add_space(8.0);
+
+        ui.add(BidiLabel::new(t.get("links-allowed")));
+        domain_list(ui, &mut self.policy.allowed);
+        ui.add(BidiLabel::new(t.get("links-blocked")));
+        domain_list(ui, &mut self.policy.blocked);
+        ui.add(BidiLabel::new(
+            RichText::new(t.get("links-domains-hint")).weak(),
+        ));
+
+        ui.add_space(4.0);
+        if ui.button(visual_order(&t.get("links-reset"))).clicked() {
+            *self = Self::default();
+        }
+    }
+}
+
+/// Edits `domains`, one per line. Blank lines are kept while editing and ignored in checks.
+fn domain_list(ui: &mut Ui, domains: &mut Vec<String>) {
+    let mut text = domains.join("\n");
+    let edit = TextEdit::multiline(&mut text)
+        .desired_rows(3)
+        .desired_width(280.0)
+        .code_editor();
+    if ui.add(edit).changed() {
+        *domains = text.split('\n').map(str::to_owned).collect();
+    }
+}
+
+/// What to do with a link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Open,
+    /// Ask first.
+    Confirm,
+    /// Never open it, for this reason.
+    Block(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct LinkPolicy {
+    /// Links to these domains and their subdomains open without asking.
+    pub allowed: Vec<String>,
+    /// Links to these domains and their subdomains never open. Wins over `allowed`.
+    pub blocked: Vec<String>,
+}
+
+impl Default for LinkPolicy {
+    fn default() -> Self {
+        let allowed = [
+            "github.com",
+            "discord.com",
+            "discord.gg",
+            "twitter.com",
+            "x.com",
+        ];
+        Self {
+            allowed: allowed.iter().map(|domain| domain.to_string()).collect(),
+            blocked: Vec::new(),
+        }
+    }
+}
+
+impl LinkPolicy {
+    pub fn check(&self, url: &str) -> Verdict {
+        let url = match Url::parse(url.trim()) {
+            Ok(url) => url,
+            Err(err) => return Verdict::Block(format!("not a valid URL: {err}")),
+        };
+        match url.scheme() {
+            "http" | "https" => {}
+            "mailto" => return Verdict::Confirm,
+            scheme => return Verdict::Block(format!("{scheme}: links aren't opened")),
+        }
+        // The host after any `user@`, so `https://github.com@evil.example` is `evil.example`.
+        let Some(host) = url.host_str() else {
+            return Verdict::Block("the link has no host".to_owned());
+        };
+        if matches_any(host, &self.blocked) {
+            Verdict::Block(format!("{host} is blocked"))
+        } else if matches_any(host, &self.allowed) {
+            Verdict::Open
+        } else {
+            Verdict::Confirm
+        }
+    }
+}
+
+/// Whether `host` is one of `domains` or a subdomain of one. The host is in punycode, as
+/// from [`Url::host_str`], and so are the domains before they are compared.
+fn matches_any(host: &str, domains: &[String]) -> bool {
+    let host = host.trim_end_matches('.');
+    domains
+        .iter()
+        .map(|domain| domain.trim().trim_start_matches("*.").trim_end_matches('.'))
+        .filter(|domain| !domain.is_empty())
+        .map(|domain| match url::Host::parse(domain) {
+            Ok(parsed) => parsed.to_string(),
+            Err(_) => domain.to_owned(),
+        })
+        .any(|domain| {
+            let domain = domain.as_str();
+            host.eq_ignore_ascii_case(domain)
+                || host.len() > domain.len()
+                    && host[host.len() - domain.len()..].eq_ignore_ascii_case(domain)
+                    && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+        })
+}
+
+#[derive(Default)]
+struct Inner {
+    policy: LinkPolicy,
+    /// Waiting for the user to confirm it.
+    pending: Option<OpenUrl>,
+    /// Whether to trust the domain of the pending link from now on.
+    trust: bool,
+    blocked: Vec<String>,
+    /// The URLs opened in this session.
+    visited: HashSet<String>,
+}
+
+impl Inner {
+    fn open_url(&mut self, ctx: &egui::Context, open: OpenUrl) {
+        self.visited.insert(open.url.clone());
+        ctx.output_mut(|o| o.open_url = Some(open));
+    }
+}
+
+/// The link policy of a context, the link waiting for confirmation and the visited links.
+/// Cheap to clone; clones share the state.
+#[derive(Clone, Default)]
+pub struct Links {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Links {
+    /// The links of `ctx`, with the default policy until [`Self::set_policy`] is called.
+    pub fn load(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_insert_with(links_id(), Self::default)
+                .clone()
+        })
+    }
+
+    pub fn set_policy(&self, policy: &LinkPolicy) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.policy != *policy {
+            inner.policy = policy.clone();
+        }
+    }
+
+    /// Opens the link, asks first, or refuses, see [`LinkPolicy`]. Natively, links always
+    /// open in the browser, whatever `new_tab` says.
+    pub fn open(&self, ctx: &egui::Context, open: OpenUrl) {
+        let mut inner = self.inner.lock().unwrap();
+        let url = &open.url;
+        match inner.policy.check(url) {
+            Verdict::Open => inner.open_url(ctx, open),
+            Verdict::Confirm => {
+                inner.pending = Some(open);
+                inner.trust = false;
+            }
+            Verdict::Block(reason) => {
+                tracing::warn!("not opening {url:?}: {reason}");
+                inner.blocked.push(url.clone());
+                ctx.request_repaint();
+            }
+        }
+    }
+
+    /// Whether `url` was opened in this session.
+    pub fn is_visited(&self, url: &str) -> bool {
+        self.inner.lock().unwrap().visited.contains(url)
+    }
+
+    /// Links that were refused since the last call, to tell the user.
+    pub fn take_blocked(&self) -> Vec<String> {
+        std::mem::take(&mut self.inner.lock().unwrap().blocked)
+    }
+
+    /// Shows the dialog for the link waiting for confirmation, if any. Returns the domain
+    /// the user chose to trust from now on, to add to the policy.
+    pub fn confirm_ui(&self, ctx: &egui::Context, t: &Translator) -> Option<String> {
+        let mut inner = self.inner.lock().unwrap();
+        let open = inner.pending.clone()?;
+        let url = &open.url;
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_owned));
+        let mut confirmed = None;
+        Window::new(visual_order(&t.get("link-confirm-title")))
+            .id(egui::Id::new("Open Link"))
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .collapsible(false)
+            .resizable(false)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.add(BidiLabel::new(t.get("link-confirm-text")));
+                ui.add_space(4.0);
+                ui.label(RichText::new(url).monospace());
+                ui.add_space(4.0);
+                if let Some(host) = &host {
+                    let trust = t.format("link-trust", &[("domain", host.into())]);
+                    ui.checkbox(&mut inner.trust, visual_order(&trust));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(visual_order(&t.get("link-open"))).clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button(visual_order(&t.get("link-cancel"))).clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+        match confirmed? {
+            true => {
+                inner.pending = None;
+                inner.open_url(ctx, open);
+                host.filter(|_| inner.trust)
+            }
+            false => {
+                inner.pending = None;
+                None
+            }
+        }
+    }
+}
+
+fn links_id() -> egui::Id {
+    egui::Id::new("links")
+}
+
+//---------------------------------------------------------------
+// I needed to make a CustomHyperlink struct to open links in new tab by default.
+// The normal Hyperlink only opens in a new tab if a keyboard modifer is active.
+// ui.add(CustomHyperlink::from_label_and_url("new tab", "about:blank"));
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct CustomHyperlink {
+    url: String,
+    text: WidgetText,
+    external_icon: bool,
+}
+
+#[allow(dead_code)]
+impl CustomHyperlink {
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new(url: impl ToString) -> Self {
+        let url = url.to_string();
+        Self {
+            url: url.clone(),
+            text: url.into(),
+            external_icon: false,
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn from_label_and_url(text: impl Into<WidgetText>, url: impl ToString) -> Self {
+        Self {
+            url: url.to_string(),
+            text: text.into(),
+            external_icon: false,
+        }
+    }
+
+    /// Whether to show an icon after the text saying the link leaves the app.
+    pub fn external_icon(mut self, external_icon: bool) -> Self {
+        self.external_icon = external_icon;
+        self
+    }
+}
+
+impl Widget for CustomHyperlink {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            url,
+            text,
+            external_icon,
+        } = self;
+        let links = Links::load(ui.ctx());
+
+        let label = Label::new(text).sense(Sense::click());
+        let (pos, text_galley, mut response) = label.layout_in_ui(ui);
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Link, text_galley.text()));
+        let icon_rect = external_icon.then(|| {
+            let size = ui.text_style_height(&TextStyle::Body) * 0.75;
+            // Part of the link for the pointer, but not another stop for the keyboard.
+            let sense = Sense {
+                focusable: false,
+                ..Sense::click()
+            };
+            let (rect, icon_response) = ui.allocate_exact_size(vec2(size, size), sense);
+            response |= icon_response;
+            rect
+        });
+
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+        }
+        if ui.is_rect_visible(response.rect) {
+            let color = if links.is_visited(&url) {
+                visited_color(ui.visuals().hyperlink_color)
+            } else {
+                ui.visuals().hyperlink_color
+            };
+            let underline = if response.hovered() || response.has_focus() {
+                Stroke::new(ui.style().interact(&response).fg_stroke.width, color)
+            } else {
+                Stroke::NONE
+            };
+            ui.painter().add(epaint::TextShape {
+                pos,
+                galley: text_galley.galley,
+                override_text_color: Some(color),
+                underline,
+                angle: 0.0,
+            });
+            if let Some(rect) = icon_rect {
+                Icon::External.paint(ui, rect, color);
+            }
+        }
+
+        // Enter and Space on the focused link are clicks too.
+        if response.clicked() || response.middle_clicked() {
+            links.open(ui.ctx(), OpenUrl::new_tab(&url));
+        }
+        let response = response.context_menu(|ui| {
+            let t = Translator::load(ui.ctx());
+            if ui.button(visual_order(&t.get("link-menu-open"))).clicked() {
+                links.open(ui.ctx(), OpenUrl::new_tab(&url));
+                ui.close_menu();
+            }
+            // Natively, links always open in the browser.
+            if cfg!(target_arch = "wasm32")
+                && ui
+                    .button(visual_order(&t.get("link-menu-open-here")))
+                    .clicked()
+            {
+                links.open(ui.ctx(), OpenUrl::same_tab(&url));
+                ui.close_menu();
+            }
+            if ui.button(visual_order(&t.get("link-menu-copy"))).clicked() {
+                ui.output_mut(|o| o.copied_text = url.clone());
+                ui.close_menu();
+            }
+        });
+        response.on_hover_text(url)
+    }
+}
+
+/// The color of a link that was opened: `color` turned towards purple, as browsers do.
+fn visited_color(color: Color32) -> Color32 {
+    let mut hsva = egui::ecolor::Hsva::from(color);
+    hsva.h = (hsva.h + 0.15).rem_euclid(1.0);
+    hsva.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{Event, Key, Modifiers, PlatformOutput, PointerButton, Pos2, RawInput, Rect, Shape};
+
+    use super::*;
+
+    /// A headless context showing one link, to check what its frames output.
+    struct Page {
+        ctx: egui::Context,
+        url: String,
+        response: Option<Response>,
+        shapes: Vec<epaint::ClippedShape>,
+        time: f64,
+    }
+
+    impl Page {
+        fn new(url: &str) -> Self {
+            Self {
+                ctx: egui::Context::default(),
+                url: url.to_owned(),
+                response: None,
+                shapes: Vec::new(),
+                time: 0.0,
+            }
+        }
+
+        fn link(&self) -> Response {
+            self.response.clone().unwrap()
+        }
+
+        fn run(&mut self, events: Vec<Event>) -> PlatformOutput {
+            self.time += 1.0 / 60.0;
+            let input = RawInput {
+                events,
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 300.0))),
+                time: Some(self.time),
+                ..Default::default()
+            };
+            let url = &self.url;
+            let mut response = None;
+            let output = self.ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    response = Some(ui.add(CustomHyperlink::new(url).external_icon(true)));
+                });
+            });
+            self.response = response;
+            self.shapes = output.shapes;
+            output.platform_output
+        }
+
+        /// Presses and releases `button` at `pos`, and returns the output of the release.
+        fn click(&mut self, pos: Pos2, button: PointerButton) -> PlatformOutput {
+            let event = |pressed| Event::PointerButton {
+                pos,
+                button,
+                pressed,
+                modifiers: Modifiers::NONE,
+            };
+            self.run(vec![Event::PointerMoved(pos), event(true)]);
+            self.run(vec![event(false)])
+        }
+
+        /// Where the text `label` is painted.
+        fn rect(&self, label: &str) -> Rect {
+            self.shapes
+                .iter()
+                .find_map(|epaint::ClippedShape(_, shape)| match shape {
+                    Shape::Text(text) if text.galley.text() == label => {
+                        Some(text.galley.rect.translate(text.pos.to_vec2()))
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| panic!("{label:?} is not shown"))
+        }
+    }
+
+    /// `OpenUrl` isn't `Debug`.
+    fn opened(output: &PlatformOutput) -> Option<(String, bool)> {
+        let open = output.open_url.as_ref()?;
+        Some((open.url.clone(), open.new_tab))
+    }
+
+    fn pending(links: &Links) -> Option<(String, bool)> {
+        let inner = links.inner.lock().unwrap();
+        let open = inner.pending.as_ref()?;
+        Some((open.url.clone(), open.new_tab))
+    }
+
+    #[test]
+    fn clicks_open_trusted_links_in_a_new_tab() {
+        let url = "https://github.com/emilk/egui";
+        let mut page = Page::new(url);
+        page.run(Vec::new());
+        let color = page.ctx.style().visuals.hyperlink_color;
+        assert!(!Links::load(&page.ctx).is_visited(url));
+
+        let center = page.link().rect.center();
+        let output = page.click(center, PointerButton::Primary);
+        assert_eq!(opened(&output), Some((url.to_owned(), true)));
+        let output = page.click(center, PointerButton::Middle);
+        assert_eq!(opened(&output), Some((url.to_owned(), true)));
+
+        // Clicking the icon opens it too.
+        let icon = page.link().rect.right_center() - vec2(2.0, 0.0);
+        let output = page.click(icon, PointerButton::Primary);
+        assert_eq!(opened(&output), Some((url.to_owned(), true)));
+
+        assert!(Links::load(&page.ctx).is_visited(url));
+        assert_ne!(visited_color(color), color);
+    }
+
+    #[test]
+    fn enter_opens_the_focused_link() {
+        let url = "https://github.com/emilk/egui";
+        let mut page = Page::new(url);
+        page.run(Vec::new());
+        let key = |pressed| Event::Key {
+            key: Key::Enter,
+            pressed,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        };
+        let output = page.run(vec![key(true), key(false)]);
+        assert_eq!(opened(&output), None);
+
+        let id = page.link().id;
+        page.ctx.memory_mut(|memory| memory.request_focus(id));
+        page.run(Vec::new());
+        let output = page.run(vec![key(true), key(false)]);
+        assert_eq!(opened(&output), Some((url.to_owned(), true)));
+    }
+
+    #[test]
+    fn context_menu_copies_and_opens_the_link() {
+        let url = "https://example.com/page";
+        let mut page = Page::new(url);
+        page.run(Vec::new());
+        let center = page.link().rect.center();
+
+        page.click(center, PointerButton::Secondary);
+        page.run(Vec::new());
+        let copy = page.rect("Copy Link Address").center();
+        let output = page.click(copy, PointerButton::Primary);
+        assert_eq!(output.copied_text, url);
+        assert_eq!(opened(&output), None);
+
+        // Not trusted, so it waits for the user to confirm. The menu closes a frame later.
+        page.run(Vec::new());
+        page.click(center, PointerButton::Secondary);
+        page.run(Vec::new());
+        let open = page.rect("Open Link").center();
+        let output = page.click(open, PointerButton::Primary);
+        assert_eq!(opened(&output), None);
+        let links = Links::load(&page.ctx);
+        assert_eq!(pending(&links), Some((url.to_owned(), true)));
+        assert!(!links.is_visited(url));
+
+        // Waiting links remember where to open.
+        links.open(&page.ctx, OpenUrl::same_tab(url));
+        assert_eq!(pending(&links), Some((url.to_owned(), false)));
+    }
+
+    #[test]
+    fn dangerous_schemes_are_blocked() {
+        let policy = LinkPolicy::default();
+        for url in [
+            "javascript:alert(1)",
+            " JavaScript:alert(1)",
+            "data:text/html,<script>alert(1)</script>",
+            "file:///etc/passwd",
+            "about:blank",
+            "vbscript:msgbox",
+            "/relative/path",
+        ] {
+            assert!(
+                matches!(policy.check(url), Verdict::Block(_)),
+                "{url} isn't blocked"
+            );
+        }
+    }
+
+    #[test]
+    fn domains_are_allowed_blocked_or_confirmed() {
+        let policy = LinkPolicy {
+            allowed: vec!["github.com".to_owned(), "".to_owned()],
+            blocked: vec!["gist.github.com".to_owned()],
+        };
+        assert_eq!(policy.check("https://github.com/emilk/egui"), Verdict::Open);
+        assert_eq!(policy.check("https://docs.GitHub.com/"), Verdict::Open);
+        assert_eq!(policy.check("https://notgithub.com/"), Verdict::Confirm);
+        assert_eq!(
+            policy.check("https://github.com@evil.example/"),
+            Verdict::Confirm
+        );
+        assert_eq!(
+            policy.check("https://github.com.evil.example/"),
+            Verdict::Confirm
+        );
+        assert_eq!(policy.check("mailto:someone@github.com"), Verdict::Confirm);
+        assert!(matches!(
+            policy.check("https://gist.github.com/x"),
+            Verdict::Block(_)
+        ));
+    }
+
+    #[test]
+    fn unicode_domains_match_their_punycode() {
+        let policy = LinkPolicy {
+            allowed: vec!["bücher.example".to_owned()],
+            blocked: vec!["*.EXAMPLE.рф".to_owned()],
+        };
+        assert_eq!(policy.check("https://bücher.example/"), Verdict::Open);
+        assert_eq!(
+            policy.check("https://shop.xn--bcher-kva.example/"),
+            Verdict::Open
+        );
+        assert!(matches!(
+            policy.check("https://www.example.рф/"),
+            Verdict::Block(_)
+        ));
+    }
+}
@@ -0,0 +1,520 @@
+//! The log console: a [`tracing`] layer keeping the latest events in memory, and a window to
+//! filter, search and export them.
+//!
+//! `main.rs` adds [`Logs::layer`] to the subscriber next to the one printing to stdout or the
+//! browser console, and passes the [`Logs`] to the app in [`crate::AppOptions`]. The layer
+//! only gets the events that pass `--log-level` (`info` by default), on native and web.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use egui::{Button, ComboBox, Label, RichText, ScrollArea, TextEdit, TextStyle, Ui};
+use tracing::field::{Field, Visit};
+use tracing::Level;
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::files::Saving;
+use crate::i18n::Translator;
+use crate::notifications::Notification;
+
+/// How many events are kept. Older ones are dropped.
+pub const CAPACITY: usize = 2000;
+
+const LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+/// A logged event.
+#[derive(Clone, Debug)]
+pub struct Record {
+    /// Numbers the events in the order they were logged.
+    pub seq: u64,
+    /// Seconds since the Unix epoch.
+    pub time: f64,
+    pub level: Level,
+    pub target: String,
+    /// The message, followed by the other fields as `name=value`.
+    pub message: String,
+}
+
+impl Record {
+    /// The record as a line of an exported log, with the date and time in UTC.
+    pub fn line(&self) -> String {
+        let date = date(self.time);
+        let time = time_of_day(self.time);
+        let Self {
+            level,
+            target,
+            message,
+            ..
+        } = self;
+        format!("{date}T{time}Z {level:>5} {target}: {message}")
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    records: VecDeque<Record>,
+    next_seq: u64,
+}
+
+/// The captured events. Cheap to clone; clones share the events.
+#[derive(Clone, Default)]
+pub struct Logs {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Logs {
+    /// A layer for the tracing subscriber, capturing the events it gets into these logs.
+    pub fn layer(&self) -> LogLayer {
+        LogLayer { logs: self.clone() }
+    }
+
+    fn push(&self, time: f64, level: Level, target: &str, message: String) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.records.len() == CAPACITY {
+            inner.records.pop_front();
+        }
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        inner.records.push_back(Record {
+            seq,
+            time,
+            level,
+            target: target.to_owned(),
+            message,
+        });
+    }
+
+    /// The number the next event will get.
+    fn next_seq(&self) -> u64 {
+        self.inner.lock().unwrap().next_seq
+    }
+
+    /// How many events are kept.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies of the kept events `filter` accepts, oldest first. Copied so no lock is held
+    /// while they are shown, which might log something.
+    pub fn records(&self, filter: impl Fn(&Record) -> bool) -> Vec<Record> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .records
+            .iter()
+            .filter(|record| filter(record))
+            .cloned()
+            .collect()
+    }
+
+    /// The numbers of the kept events `filter` accepts, oldest first.
+    pub fn matching(&self, filter: impl Fn(&Record) -> bool) -> Vec<u64> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .records
+            .iter()
+            .filter(|record| filter(record))
+            .map(|record| record.seq)
+            .collect()
+    }
+
+    /// Copies of the events numbered `seqs`, in ascending order, skipping those no longer
+    /// kept.
+    pub fn get(&self, seqs: &[u64]) -> Vec<Record> {
+        let inner = self.inner.lock().unwrap();
+        let Some(first) = inner.records.front().map(|record| record.seq) else {
+            return Vec::new();
+        };
+        // The kept events are numbered without gaps.
+        seqs.iter()
+            .filter_map(|seq| inner.records.get(seq.checked_sub(first)? as usize))
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().records.clear();
+    }
+}
+
+/// Captures events into [`Logs`], see [`Logs::layer`].
+pub struct LogLayer {
+    logs: Logs,
+}
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for LogLayer {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.logs.push(
+            now(),
+            *metadata.level(),
+            metadata.target(),
+            visitor.into_message(),
+        );
+    }
+}
+
+/// Collects the fields of an event into a message.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn add(&mut self, field: &Field, value: std::fmt::Arguments<'_>) {
+        if field.name() == "message" {
+            let _ = self.message.write_fmt(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn into_message(self) -> String {
+        let Self { message, fields } = self;
+        if message.is_empty() {
+            fields.trim_start().to_owned()
+        } else {
+            message + &fields
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.add(field, format_args!("{value}"));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.add(field, format_args!("{value:?}"));
+    }
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now() / 1000.0
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |since| since.as_secs_f64())
+    }
+}
+
+/// The UTC date of `time`, in seconds since the Unix epoch, as `YYYY-MM-DD`.
+fn date(time: f64) -> String {
+    // Howard Hinnant's days-to-civil algorithm.
+    let days = (time / 86_400.0).floor() as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// The UTC time of day of `time`, in seconds since the Unix epoch, as `HH:MM:SS.mmm`.
+fn time_of_day(time: f64) -> String {
+    let millis = (time.rem_euclid(86_400.0) * 1000.0) as u64;
+    let (seconds, millis) = (millis / 1000, millis % 1000);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    format!("{hours:02}:{minutes:02}:{seconds:02}.{millis:03}")
+}
+
+/// The log console window's contents and filters.
+pub struct LogConsole {
+    logs: Logs,
+    /// The most verbose level shown.
+    level: Level,
+    /// Only events whose target contains this are shown.
+    target: String,
+    /// Only events whose message contains this, ignoring case, are shown.
+    query: String,
+    /// While paused, events from this one on aren't shown yet.
+    paused_at: Option<u64>,
+    auto_scroll: bool,
+    /// The last export, while the user picks where it goes.
+    exporting: Option<Saving>,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self::new(Logs::default())
+    }
+}
+
+impl LogConsole {
+    pub fn new(logs: Logs) -> Self {
+        Self {
+            logs,
+            level: Level::TRACE,
+            target: String::new(),
+            query: String::new(),
+            paused_at: None,
+            auto_scroll: true,
+            exporting: None,
+        }
+    }
+
+    /// Whether a record passes the filters.
+    fn filter(&self) -> impl Fn(&Record) -> bool + '_ {
+        let query = self.query.trim().to_lowercase();
+        let target = self.target.trim();
+        move |record| {
+            record.level <= self.level
+                && record.target.contains(target)
+                && (query.is_empty() || record.message.to_lowercase().contains(&query))
+                && self
+                    .paused_at
+                    .map_or(true, |paused_at| record.seq < paused_at)
+        }
+    }
+
+    /// Shows the filters and the events that pass them.
+    pub fn ui(&mut self, ui: &mut Ui, t: &Translator) {
+        ui.horizontal_wrapped(|ui| {
+            ComboBox::from_id_source("log_level")
+                .selected_text(self.level.as_str())
+                .show_ui(ui, |ui| {
+                    for level in LEVELS {
+                        ui.selectable_value(&mut self.level, level, level.as_str());
+                    }
+                })
+                .response
+                .on_hover_text(visual_order(&t.get("log-level")));
+            ui.add(
+                TextEdit::singleline(&mut self.target)
+                    .hint_text(visual_order(&t.get("log-target")))
+                    .desired_width(120.0),
+            );
+            ui.add(
+                TextEdit::singleline(&mut self.query)
+                    .hint_text(visual_order(&t.get("log-search")))
+                    .desired_width(160.0),
+            );
+            let mut paused = self.paused_at.is_some();
+            if ui
+                .toggle_value(&mut paused, visual_order(&t.get("log-pause")))
+                .changed()
+            {
+                self.paused_at = paused.then(|| self.logs.next_seq());
+            }
+            ui.checkbox(
+                &mut self.auto_scroll,
+                visual_order(&t.get("log-auto-scroll")),
+            );
+            let export = Button::new(visual_order(&t.get("log-export-ellipsis")));
+            if ui.add_enabled(self.exporting.is_none(), export).clicked() {
+                self.export(ui.ctx());
+            }
+            if ui.button(visual_order(&t.get("log-clear"))).clicked() {
+                self.logs.clear();
+            }
+        });
+
+        let shown = self.logs.matching(self.filter());
+        let total = self.logs.len();
+        ui.add(BidiLabel::new(
+            RichText::new(t.format(
+                "log-count",
+                &[("shown", shown.len().into()), ("total", total.into())],
+            ))
+            .weak(),
+        ));
+        ui.separator();
+
+        if shown.is_empty() {
+            ui.add(BidiLabel::new(RichText::new(t.get("log-empty")).weak()));
+        }
+        let row_height = ui.text_style_height(&TextStyle::Monospace);
+        ScrollArea::both()
+            .auto_shrink([false, false])
+            .stick_to_bottom(self.auto_scroll)
+            .show_rows(ui, row_height, shown.len(), |ui, rows| {
+                // Only the rows in view are copied.
+                for record in self.logs.get(&shown[rows]) {
+                    record_ui(ui, &record);
+                }
+            });
+
+        // New events don't ask for a repaint: the layer can't reach the context, and egui
+        // may be the one logging.
+        if self.paused_at.is_none() {
+            ui.ctx().request_repaint_after(Duration::from_millis(500));
+        }
+    }
+
+    /// Starts saving all kept events, not only the shown ones, to a text file.
+    fn export(&mut self, ctx: &egui::Context) {
+        let mut text = String::new();
+        for record in self.logs.records(|_| true) {
+            text += &record.line();
+            text.push('\n');
+        }
+        let filter = ("Text", &["txt", "log"][..]);
+        self.exporting = Some(crate::files::save_file(
+            ctx,
+            "log.txt",
+            filter,
+            text.into_bytes(),
+        ));
+    }
+
+    /// A notification for the user about the last export, once it finished. Called every
+    /// frame, since the window may be closed by then.
+    pub fn take_notification(&mut self, t: &Translator) -> Option<Notification> {
+        let saved = self.exporting.as_ref()?.take()?;
+        self.exporting = None;
+        match saved {
+            Ok(Some(location)) => Some(Notification::info(
+                t.format("log-exported", &[("location", (&location).into())]),
+            )),
+            Ok(None) => None,
+            Err(err) => Some(Notification::error(err.message(t))),
+        }
+    }
+}
+
+fn record_ui(ui: &mut Ui, record: &Record) {
+    let visuals = ui.visuals();
+    let level_color = match record.level {
+        Level::ERROR => visuals.error_fg_color,
+        Level::WARN => visuals.warn_fg_color,
+        Level::INFO => visuals.text_color(),
+        _ => visuals.weak_text_color(),
+    };
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(time_of_day(record.time)).monospace().weak());
+        let level = format!("{:>5}", record.level.as_str());
+        ui.label(RichText::new(level).monospace().color(level_color));
+        ui.label(RichText::new(&record.target).monospace().weak());
+        // One row per event, for `show_rows`.
+        let message = record.message.replace('\n', " ");
+        ui.add(Label::new(RichText::new(message).monospace()).wrap(false));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    /// Logs the events of `log` into new [`Logs`].
+    fn capture(log: impl FnOnce()) -> Logs {
+        let logs = Logs::default();
+        let subscriber = tracing_subscriber::registry().with(logs.layer());
+        tracing::subscriber::with_default(subscriber, log);
+        logs
+    }
+
+    #[test]
+    fn events_are_captured_with_their_fields() {
+        let logs = capture(|| {
+            tracing::info!(target: "app::network", "connected to {}", "pulsar-3");
+            tracing::warn!(status = 404, path = "/x", "request failed");
+            tracing::debug!(code_id = 7);
+        });
+        let records = logs.records(|_| true);
+        let messages: Vec<_> = records.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "connected to pulsar-3",
+                "request failed status=404 path=/x",
+                "code_id=7",
+            ]
+        );
+        assert_eq!(records[0].target, "app::network");
+        assert_eq!(records[1].level, Level::WARN);
+        assert!(records[0].seq < records[1].seq);
+    }
+
+    #[test]
+    fn old_events_are_dropped() {
+        let logs = capture(|| {
+            for i in 0..CAPACITY + 5 {
+                tracing::info!("event {i}");
+            }
+        });
+        assert_eq!(logs.len(), CAPACITY);
+        let records = logs.records(|_| true);
+        assert_eq!(records[0].message, "event 5");
+        assert_eq!(records[0].seq, 5);
+
+        let kept: Vec<_> = logs.get(&[0, 5, 9]).into_iter().map(|r| r.seq).collect();
+        assert_eq!(kept, [5, 9]);
+        let last = CAPACITY as u64 + 4;
+        assert_eq!(logs.get(&[last, last + 1]).len(), 1);
+    }
+
+    #[test]
+    fn filters_and_pause() {
+        let logs = capture(|| {
+            tracing::error!(target: "app::storage", "Quota exceeded");
+            tracing::info!(target: "app::network", "connected");
+            tracing::trace!(target: "app::network", "frame");
+        });
+        let mut console = LogConsole::new(logs.clone());
+        let shown = |console: &LogConsole| logs.matching(console.filter()).len();
+        assert_eq!(shown(&console), 3);
+
+        console.level = Level::INFO;
+        assert_eq!(shown(&console), 2);
+        console.target = "network".to_owned();
+        assert_eq!(shown(&console), 1);
+        console.target.clear();
+        console.query = " QUOTA ".to_owned();
+        assert_eq!(shown(&console), 1);
+        console.query.clear();
+
+        console.paused_at = Some(logs.next_seq());
+        tracing::subscriber::with_default(
+            tracing_subscriber::registry().with(logs.layer()),
+            || tracing::info!("later"),
+        );
+        assert_eq!(shown(&console), 2);
+        console.paused_at = None;
+        assert_eq!(shown(&console), 3);
+    }
+
+    #[test]
+    fn exported_lines_have_the_date_and_time() {
+        let record = Record {
+            seq: 0,
+            // 2024-02-29 13:05:09.250 UTC
+            time: 1_709_211_909.25,
+            level: Level::WARN,
+            target: "app".to_owned(),
+            message: "low on space".to_owned(),
+        };
+        assert_eq!(
+            record.line(),
+            "2024-02-29T13:05:09.250Z  WARN app: low on space"
+        );
+    }
+}
@@ -1,24 +1,62 @@
 #![warn(clippy::all, rust_2018_idioms)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+#[cfg(not(target_arch = "wasm32"))]
+use eframe::egui;
+
 // When compiling natively:
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    // Log to stdout (if you run with `RUST_LOG=debug`).
-    tracing_subscriber::fmt::init();
+    let mut cli = match cli::Cli::from_env() {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("{err}\n\n{}", cli::HELP);
+            std::process::exit(2);
+        }
+    };
+    if cli.help {
+        print!("{}", cli::HELP);
+        return Ok(());
+    }
+    if cli.print_theme {
+        println!("{}", cli::built_in_theme());
+        return Ok(());
+    }
+
+    // Log to stdout (if you run with `--log-level debug`), and to the log console of the app.
+    {
+        use tracing_subscriber::prelude::*;
+        let level = cli
+            .log_level
+            .unwrap_or(tracing_subscriber::filter::LevelFilter::INFO);
+        tracing_subscriber::registry()
+            .with(level)
+            .with(tracing_subscriber::fmt::layer())
+            .with(cli.app.logs.layer())
+            .init();
+    }
+
+    if let Err(err) = cli.load_theme() {
+        eprintln!("{err}");
+        std::process::exit(2);
+    }
 
     let native_options = eframe::NativeOptions {
         drag_and_drop_support: true,
-        initial_window_size: Some([640.0, 480.0].into()),
-        centered: true,
+        initial_window_size: Some(cli.window_size.unwrap_or(egui::vec2(640.0, 480.0))),
+        initial_window_pos: cli.window_position,
+        centered: cli.window_position.is_none(),
         follow_system_theme: false,
         default_theme: eframe::Theme::Dark,
         ..Default::default()
     };
     eframe::run_native(
-        "eframe native app",
+        eframe_template::APP_NAME,
         native_options,
-        Box::new(|cc| Box::new(eframe_template::TemplateApp::new(cc))),
+        Box::new(|cc| Box::new(eframe_template::TemplateApp::new(cc, cli.app))),
     )
 }
 
@@ -28,8 +66,20 @@ fn main() {
     // Make sure panics are logged using `console.error`.
     console_error_panic_hook::set_once();
 
-    // Redirect tracing to console.log and friends:
-    tracing_wasm::set_as_global_default();
+    // Redirect tracing to console.log and friends, and to the log console of the app:
+    let options = eframe_template::AppOptions::default();
+    {
+        use tracing_subscriber::prelude::*;
+        tracing_subscriber::registry()
+            .with(tracing_wasm::WASMLayer::default())
+            .with(
+                options
+                    .logs
+                    .layer()
+                    .with_filter(tracing_subscriber::filter::LevelFilter::INFO),
+            )
+            .init();
+    }
 
     let web_options = eframe::WebOptions::default();
 
@@ -37,7 +87,7 @@ fn main() {
         eframe::start_web(
             "the_canvas_id", // hardcode it
             web_options,
-            Box::new(|cc| Box::new(eframe_template::TemplateApp::new(cc))),
+            Box::new(|cc| Box::new(eframe_template::TemplateApp::new(cc, options))),
         )
         .await
         .expect("failed to start eframe");
@@ -0,0 +1,569 @@
+//! Shows markdown: headings, emphasis, code, lists, quotes, tables, images and links.
+//!
+//! Headings use the named text styles (`#` is [`AppTextStyle::Title`], `##` is
+//! [`AppTextStyle::Subtitle`]), code has the theme's `code_bg_color`, links open like
+//! [`CustomHyperlink`]s, through the link policy, and images are asset ids, see
+//! [`crate::assets`].
+//!
+//! ```ignore
+//! Markdown::new("release notes", "# 0.2\n\n- **Faster** startup").show(ui);
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use egui::{
+    Align, FontFamily, Frame, Grid, Id, Label, Layout, Response, RichText, ScrollArea, Stroke,
+    TextStyle, Ui, WidgetText,
+};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+
+use crate::assets::Assets;
+use crate::direction::{has_rtl, wrap_rtl, Direction};
+use crate::links::CustomHyperlink;
+use crate::text_styles::{AppTextStyle, RichTextExt};
+
+#[must_use = "You should call .show()"]
+pub struct Markdown<'a> {
+    id_source: Id,
+    source: &'a str,
+    image_height: f32,
+}
+
+impl<'a> Markdown<'a> {
+    pub fn new(id_source: impl Hash, source: &'a str) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            source,
+            image_height: 64.0,
+        }
+    }
+
+    /// Height of images, in points. Default: 64.
+    pub fn image_height(mut self, image_height: f32) -> Self {
+        self.image_height = image_height;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let id = ui.make_persistent_id(self.id_source);
+        let blocks = Parsed::cached(ui, id, self.source);
+        let bold = FontFamily::Name(crate::style::BOLD_FAMILY.into());
+        let writer = Writer {
+            image_height: self.image_height,
+            bold: ui.fonts(|f| f.families().contains(&bold)).then_some(bold),
+        };
+        ui.vertical(|ui| writer.blocks(ui, id, &blocks.blocks))
+            .response
+    }
+}
+
+/// The blocks of a source, kept with the widget between frames.
+struct Parsed {
+    /// The hash of the source.
+    source: u64,
+    blocks: Vec<Block>,
+}
+
+impl Parsed {
+    fn cached(ui: &Ui, id: Id, source: &str) -> Arc<Self> {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        let hash = hasher.finish();
+        let cache_id = id.with("parsed");
+        if let Some(cached) = ui.data_mut(|d| d.get_temp::<Arc<Parsed>>(cache_id)) {
+            if cached.source == hash {
+                return cached;
+            }
+        }
+        let parsed = Arc::new(Self {
+            source: hash,
+            blocks: parse(source),
+        });
+        ui.data_mut(|d| d.insert_temp(cache_id, parsed.clone()));
+        parsed
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Paragraph(Vec<Inline>),
+    Heading(HeadingLevel, Vec<Inline>),
+    Code(String),
+    /// Numbered from `start`, or with bullets.
+    List {
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Quote(Vec<Block>),
+    Table {
+        header: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    Rule,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Inline {
+    Text(Span),
+    /// An asset id.
+    Image {
+        id: String,
+        alt: String,
+    },
+    Break,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Span {
+    text: String,
+    strong: bool,
+    emphasis: bool,
+    strikethrough: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+/// What is being parsed: blocks or cells, and the inlines of the paragraph they end with.
+#[derive(Default)]
+struct Container {
+    blocks: Vec<Block>,
+    inlines: Vec<Inline>,
+}
+
+impl Container {
+    /// Makes a paragraph of text outside of one, as in the items of tight lists.
+    fn flush(&mut self) {
+        if !self.inlines.is_empty() {
+            let inlines = std::mem::take(&mut self.inlines);
+            self.blocks.push(Block::Paragraph(inlines));
+        }
+    }
+}
+
+fn parse(source: &str) -> Vec<Block> {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let mut stack = vec![Container::default()];
+    // Finished items of the open lists, and table rows.
+    let mut lists: Vec<Vec<Vec<Block>>> = Vec::new();
+    let mut rows: Vec<Vec<Vec<Inline>>> = Vec::new();
+    let mut cells: Vec<Vec<Inline>> = Vec::new();
+    let mut style = Span::default();
+    let mut image: Option<(String, String)> = None;
+    // The text of the open code block, which comes in a piece for each line when indented.
+    let mut code: Option<String> = None;
+
+    for event in Parser::new_ext(source, options) {
+        let top = stack.last_mut().unwrap();
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph | Tag::Heading(..) => top.flush(),
+                Tag::BlockQuote | Tag::Item | Tag::TableCell => {
+                    top.flush();
+                    stack.push(Container::default());
+                }
+                Tag::List(_) => {
+                    top.flush();
+                    lists.push(Vec::new());
+                }
+                Tag::Emphasis => style.emphasis = true,
+                Tag::Strong => style.strong = true,
+                Tag::Strikethrough => style.strikethrough = true,
+                Tag::Link(_, url, _) => style.link = Some(url.to_string()),
+                Tag::Image(_, url, _) => image = Some((url.to_string(), String::new())),
+                Tag::CodeBlock(_) => {
+                    top.flush();
+                    code = Some(String::new());
+                }
+                Tag::FootnoteDefinition(_) | Tag::Table(_) | Tag::TableHead | Tag::TableRow => {}
+            },
+            Event::End(tag) => match tag {
+                Tag::Paragraph => top.flush(),
+                Tag::Heading(level, ..) => {
+                    let inlines = std::mem::take(&mut top.inlines);
+                    top.blocks.push(Block::Heading(level, inlines));
+                }
+                Tag::CodeBlock(kind) => {
+                    let code = code.take().unwrap_or_default();
+                    let code = match kind {
+                        CodeBlockKind::Fenced(_) => code.trim_end_matches('\n').to_owned(),
+                        CodeBlockKind::Indented => code.trim_end().to_owned(),
+                    };
+                    top.blocks.push(Block::Code(code));
+                }
+                Tag::BlockQuote => {
+                    let mut quote = stack.pop().unwrap();
+                    quote.flush();
+                    let top = stack.last_mut().unwrap();
+                    top.blocks.push(Block::Quote(quote.blocks));
+                }
+                Tag::Item => {
+                    let mut item = stack.pop().unwrap();
+                    item.flush();
+                    lists.last_mut().unwrap().push(item.blocks);
+                }
+                Tag::List(start) => {
+                    let items = lists.pop().unwrap();
+                    top.blocks.push(Block::List { start, items });
+                }
+                Tag::TableCell => {
+                    let cell = stack.pop().unwrap();
+                    cells.push(cell.inlines);
+                }
+                Tag::TableHead | Tag::TableRow => rows.push(std::mem::take(&mut cells)),
+                Tag::Table(_) => {
+                    let mut all = std::mem::take(&mut rows).into_iter();
+                    let header = all.next().unwrap_or_default();
+                    let rows = all.collect();
+                    top.blocks.push(Block::Table { header, rows });
+                }
+                Tag::Emphasis => style.emphasis = false,
+                Tag::Strong => style.strong = false,
+                Tag::Strikethrough => style.strikethrough = false,
+                Tag::Link(..) => style.link = None,
+                Tag::Image(..) => {
+                    if let Some((id, alt)) = image.take() {
+                        top.inlines.push(Inline::Image { id, alt });
+                    }
+                }
+                Tag::FootnoteDefinition(_) => {}
+            },
+            Event::Text(text) => match (&mut image, &mut code) {
+                (Some((_, alt)), _) => alt.push_str(&text),
+                (None, Some(code)) => code.push_str(&text),
+                (None, None) if text.is_empty() => {}
+                (None, None) => top.inlines.push(Inline::Text(Span {
+                    text: text.to_string(),
+                    ..style.clone()
+                })),
+            },
+            Event::Code(code) => top.inlines.push(Inline::Text(Span {
+                text: code.to_string(),
+                code: true,
+                ..style.clone()
+            })),
+            Event::SoftBreak => top.inlines.push(Inline::Text(Span {
+                text: " ".to_owned(),
+                ..style.clone()
+            })),
+            Event::HardBreak => top.inlines.push(Inline::Break),
+            Event::Rule => {
+                top.flush();
+                top.blocks.push(Block::Rule);
+            }
+            // Raw HTML, footnotes and task lists aren't supported.
+            Event::Html(_) | Event::FootnoteReference(_) | Event::TaskListMarker(_) => {}
+        }
+    }
+    let mut root = stack.pop().unwrap();
+    root.flush();
+    root.blocks
+}
+
+struct Writer {
+    image_height: f32,
+    /// The bold font, if the fonts have it.
+    bold: Option<FontFamily>,
+}
+
+impl Writer {
+    /// The ids of the blocks are made from the id of the list item or quote they are in,
+    /// `parent`, so they are unique in the document.
+    fn blocks(&self, ui: &mut Ui, parent: Id, blocks: &[Block]) {
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 {
+                ui.add_space(4.0);
+            }
+            self.block(ui, parent.with(index), block);
+        }
+    }
+
+    fn block(&self, ui: &mut Ui, id: Id, block: &Block) {
+        match block {
+            Block::Paragraph(inlines) => self.inlines(ui, inlines, None),
+            Block::Heading(level, inlines) => {
+                ui.add_space(4.0);
+                self.inlines(ui, inlines, Some(*level));
+            }
+            Block::Code(code) => {
+                Frame::none()
+                    .fill(ui.visuals().code_bg_color)
+                    .rounding(4.0)
+                    .inner_margin(6.0)
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+                        ScrollArea::horizontal().id_source(id).show(ui, |ui| {
+                            ui.add(Label::new(RichText::new(code).monospace()).wrap(false));
+                        });
+                    });
+            }
+            Block::List { start, items } => {
+                for (number, item) in items.iter().enumerate() {
+                    let marker = match start {
+                        Some(start) => format!("{}.", start + number as u64),
+                        None => "•".to_owned(),
+                    };
+                    ui.horizontal_top(|ui| {
+                        let width = ui.spacing().indent;
+                        ui.allocate_ui_with_layout(
+                            egui::vec2(width, 0.0),
+                            Layout::top_down(Align::Max),
+                            |ui| {
+                                ui.set_min_width(width);
+                                ui.label(marker);
+                            },
+                        );
+                        ui.vertical(|ui| self.blocks(ui, id.with(number), item));
+                    });
+                }
+            }
+            Block::Quote(blocks) => {
+                let response = ui.horizontal_top(|ui| {
+                    ui.add_space(12.0);
+                    ui.vertical(|ui| self.blocks(ui, id, blocks));
+                });
+                let rect = response.response.rect;
+                let stroke = Stroke::new(3.0, ui.visuals().weak_text_color());
+                let x = rect.left() + 4.0;
+                ui.painter().vline(x, rect.y_range(), stroke);
+            }
+            Block::Table { header, rows } => {
+                Grid::new(id).striped(true).show(ui, |ui| {
+                    for cell in header {
+                        ui.horizontal_wrapped(|ui| self.inlines(ui, cell, Some(HeadingLevel::H6)));
+                    }
+                    ui.end_row();
+                    for row in rows {
+                        for cell in row {
+                            ui.horizontal_wrapped(|ui| self.inlines(ui, cell, None));
+                        }
+                        ui.end_row();
+                    }
+                });
+            }
+            Block::Rule => {
+                ui.separator();
+            }
+        }
+    }
+
+    /// Lays out text, links and images in rows, in the style of a heading at `level`.
+    fn inlines(&self, ui: &mut Ui, inlines: &[Inline], level: Option<HeadingLevel>) {
+        let rtl = inlines
+            .iter()
+            .any(|inline| matches!(inline, Inline::Text(span) if has_rtl(&span.text)));
+        if rtl {
+            self.rtl_inlines(ui, inlines, level);
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            // The spaces are in the text.
+            ui.spacing_mut().item_spacing.x = 0.0;
+            for inline in inlines {
+                match inline {
+                    Inline::Text(span) => self.span(ui, &span.text, span, level),
+                    Inline::Image { id, alt } => self.image(ui, id, alt),
+                    Inline::Break => ui.end_row(),
+                }
+            }
+        });
+    }
+
+    /// Like [`Self::inlines`] for right-to-left text, which is wrapped before each row is
+    /// reordered, see [`wrap_rtl`].
+    fn rtl_inlines(&self, ui: &mut Ui, inlines: &[Inline], level: Option<HeadingLevel>) {
+        // Images are object replacement characters in the text, breaks are newlines.
+        let sections: Vec<&str> = inlines
+            .iter()
+            .map(|inline| match inline {
+                Inline::Text(span) => span.text.as_str(),
+                Inline::Image { .. } => "\u{FFFC}",
+                Inline::Break => "\n",
+            })
+            .collect();
+        let rows = wrap_rtl(
+            &sections,
+            ui.available_width(),
+            |section, text| match &inlines[section] {
+                Inline::Text(span) => {
+                    let text = WidgetText::from(self.rich_text(ui, text, span, level));
+                    let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Body);
+                    galley.size().x
+                }
+                _ => self.image_height,
+            },
+        );
+        for row in rows {
+            Direction::RightToLeft.horizontal(ui, |ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                for (section, text) in &row {
+                    match &inlines[*section] {
+                        Inline::Text(span) => self.span(ui, text, span, level),
+                        Inline::Image { id, alt } => self.image(ui, id, alt),
+                        Inline::Break => {}
+                    }
+                }
+            });
+        }
+    }
+
+    /// Shows `text` of `span`, which may be a piece of it.
+    fn span(&self, ui: &mut Ui, text: &str, span: &Span, level: Option<HeadingLevel>) {
+        let text = self.rich_text(ui, text, span, level);
+        match &span.link {
+            Some(url) => {
+                ui.add(CustomHyperlink::from_label_and_url(text, url));
+            }
+            None => {
+                ui.label(text);
+            }
+        }
+    }
+
+    fn image(&self, ui: &mut Ui, id: &str, alt: &str) {
+        let response = Assets::load(ui.ctx()).image(ui, id, self.image_height, None);
+        if !alt.is_empty() {
+            response.on_hover_text(alt);
+        }
+    }
+
+    fn rich_text(&self, ui: &Ui, text: &str, span: &Span, level: Option<HeadingLevel>) -> RichText {
+        let mut text = RichText::new(text);
+        let mut strong = span.strong;
+        text = match level {
+            Some(HeadingLevel::H1) => text.app_style(ui, AppTextStyle::Title),
+            Some(HeadingLevel::H2) => text.app_style(ui, AppTextStyle::Subtitle),
+            Some(HeadingLevel::H3) => text.text_style(TextStyle::Heading),
+            Some(_) => {
+                strong = true;
+                text
+            }
+            None => text,
+        };
+        if strong {
+            text = text.strong();
+            if let Some(bold) = &self.bold {
+                text = text.family(bold.clone());
+            }
+        }
+        if span.emphasis {
+            text = text.italics();
+        }
+        if span.strikethrough {
+            text = text.strikethrough();
+        }
+        if span.code {
+            text = text.code().family(FontFamily::Monospace);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Inline {
+        Inline::Text(Span {
+            text: text.to_owned(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn blocks_and_styles_are_parsed() {
+        let source = "# Title\n\nSome *emphasis*, `code` and [a link](https://example.com).\n\n\
+                      - one\n- two\n\n  > quoted\n\n```\nlet x = 1;\n```\n\n---\n";
+        let blocks = parse(source);
+        assert_eq!(
+            blocks[0],
+            Block::Heading(HeadingLevel::H1, vec![text("Title")])
+        );
+        let Block::Paragraph(inlines) = &blocks[1] else {
+            panic!("{:?}", blocks[1]);
+        };
+        let spans: Vec<&Span> = inlines
+            .iter()
+            .map(|inline| match inline {
+                Inline::Text(span) => span,
+                other => panic!("{other:?}"),
+            })
+            .collect();
+        assert!(spans[1].emphasis && spans[1].text == "emphasis");
+        assert!(spans[3].code && spans[3].text == "code");
+        assert_eq!(spans[5].link.as_deref(), Some("https://example.com"));
+        assert_eq!(
+            blocks[2],
+            Block::List {
+                start: None,
+                items: vec![
+                    vec![Block::Paragraph(vec![text("one")])],
+                    vec![
+                        Block::Paragraph(vec![text("two")]),
+                        Block::Quote(vec![Block::Paragraph(vec![text("quoted")])]),
+                    ],
+                ],
+            }
+        );
+        assert_eq!(blocks[3], Block::Code("let x = 1;".to_owned()));
+        assert_eq!(blocks[4], Block::Rule);
+    }
+
+    #[test]
+    fn code_blocks_keep_all_their_lines() {
+        let source = "Indented:\n\n    let x = 1;\n    let y = 2;\n\n\n    x + y\n\nFenced:\n\n```\na\n\nb\n```";
+        let blocks = parse(source);
+        assert_eq!(
+            blocks[1],
+            Block::Code("let x = 1;\nlet y = 2;\n\n\nx + y".to_owned())
+        );
+        assert_eq!(blocks[3], Block::Code("a\n\nb".to_owned()));
+    }
+
+    #[test]
+    fn tables_and_images_are_parsed() {
+        let source = "| Key | Value |\n|---|---|\n| a | **1** |\n\n![The logo](scrt)";
+        let blocks = parse(source);
+        let strong = Inline::Text(Span {
+            text: "1".to_owned(),
+            strong: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            blocks[0],
+            Block::Table {
+                header: vec![vec![text("Key")], vec![text("Value")]],
+                rows: vec![vec![vec![text("a")], vec![strong]]],
+            }
+        );
+        let image = Inline::Image {
+            id: "scrt".to_owned(),
+            alt: "The logo".to_owned(),
+        };
+        assert_eq!(blocks[1], Block::Paragraph(vec![image]));
+    }
+
+    #[test]
+    fn blocks_in_sibling_list_items_have_their_own_ids() {
+        let source = "- one\n\n  ```\n  a\n  ```\n- two\n\n  ```\n  b\n  ```\n";
+        let ctx = egui::Context::default();
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                Markdown::new("list", source).show(ui);
+            });
+        });
+        // egui paints a warning over widgets that share an id.
+        let clashes: Vec<_> = output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.1 {
+                egui::Shape::Text(text) if text.galley.text().starts_with('🔥') => {
+                    Some(text.galley.text().to_owned())
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(clashes.is_empty(), "{clashes:?}");
+    }
+}
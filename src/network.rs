@@ -0,0 +1,51 @@
+/// The Secret Network chains the app knows how to talk to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+    Local,
+}
+
+impl Network {
+    pub const ALL: [Network; 3] = [Network::Mainnet, Network::Testnet, Network::Local];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Mainnet => "Mainnet",
+            Network::Testnet => "Testnet",
+            Network::Local => "Local",
+        }
+    }
+
+    /// Parses a name or chain id, e.g. `testnet` or `pulsar-3`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|network| {
+            network.name().eq_ignore_ascii_case(name)
+                || network.chain_id().eq_ignore_ascii_case(name)
+        })
+    }
+
+    pub fn chain_id(self) -> &'static str {
+        match self {
+            Network::Mainnet => "secret-4",
+            Network::Testnet => "pulsar-3",
+            Network::Local => "secretdev-1",
+        }
+    }
+
+    /// Base url of the LCD (REST) endpoint, without a trailing slash.
+    pub fn lcd_url(self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://lcd.mainnet.secretsaturn.net",
+            Network::Testnet => "https://lcd.testnet.secretsaturn.net",
+            Network::Local => "http://localhost:1317",
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name(), self.chain_id())
+    }
+}
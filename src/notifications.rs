@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use egui::{Color32, RichText, Ui};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Something the user can trigger by clicking a notification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationAction {
+    /// Reload the page into the new build waiting in the service worker.
+    Reload,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub text: String,
+    pub level: Level,
+    pub action: Option<NotificationAction>,
+}
+
+impl Notification {
+    pub fn new(level: Level, text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            level,
+            action: None,
+        }
+    }
+
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(Level::Info, text)
+    }
+
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self::new(Level::Warning, text)
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(Level::Error, text)
+    }
+
+    pub fn with_action(mut self, action: NotificationAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+}
+
+/// Notifications shown one at a time in the top bar, newest first.
+#[derive(Default)]
+pub struct Notifications {
+    queue: VecDeque<Notification>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, notification: Notification) {
+        self.queue.retain(|queued| queued.text != notification.text);
+        self.queue.push_front(notification);
+    }
+
+    /// Shows the newest notification. Returns its action if the user clicked it.
+    pub fn ui(&mut self, ui: &mut Ui) -> Option<NotificationAction> {
+        let notification = self.queue.front()?;
+        let color = match notification.level {
+            Level::Info => Color32::from_rgb(242, 176, 70),
+            Level::Warning => ui.visuals().warn_fg_color,
+            Level::Error => ui.visuals().error_fg_color,
+        };
+        let text = |text: &str| RichText::new(text).color(color).italics();
+
+        let mut clicked = None;
+        let mut dismissed = false;
+        ui.horizontal(|ui| {
+            if self.queue.len() > 1 {
+                ui.weak(format!("(+{})", self.queue.len() - 1));
+            }
+            match notification.action {
+                Some(action) => {
+                    ui.style_mut().visuals.hyperlink_color = color;
+                    if ui.link(text(&visual_order(&notification.text))).clicked() {
+                        clicked = Some(action);
+                    }
+                }
+                None => {
+                    ui.add(BidiLabel::new(text(&notification.text)));
+                }
+            }
+            let dismiss = Translator::load(ui.ctx()).get("notification-dismiss");
+            dismissed = ui
+                .small_button("✖")
+                .on_hover_text(visual_order(&dismiss))
+                .clicked();
+        });
+
+        if clicked.is_some() || dismissed {
+            self.queue.pop_front();
+        }
+        clicked
+    }
+}
@@ -0,0 +1,133 @@
+//! Talks to the service worker in `assets/sw.js`, so the app can offer to reload
+//! when a new build has been downloaded in the background.
+//!
+//! On native this does nothing.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone, Default)]
+pub struct UpdateWatcher {
+    available: Arc<AtomicBool>,
+}
+
+impl UpdateWatcher {
+    /// Returns `true` once per newly installed build.
+    pub fn take_update_available(&self) -> bool {
+        self.available.swap(false, Ordering::Relaxed)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl UpdateWatcher {
+    pub fn start(_ctx: &egui::Context) -> Self {
+        Self::default()
+    }
+
+    pub fn apply(&self) {}
+}
+
+#[cfg(target_arch = "wasm32")]
+impl UpdateWatcher {
+    pub fn start(ctx: &egui::Context) -> Self {
+        use wasm_bindgen::{closure::Closure, JsCast as _};
+
+        let watcher = Self::default();
+        let Some(container) = web::service_worker_container() else {
+            return watcher;
+        };
+
+        // Posted by the worker's `install` handler.
+        let on_message = {
+            let watcher = watcher.clone();
+            let ctx = ctx.clone();
+            let container = container.clone();
+            Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+                let kind = js_sys::Reflect::get(&event.data(), &"type".into())
+                    .ok()
+                    .and_then(|kind| kind.as_string());
+                // The very first install isn't an update.
+                if kind.as_deref() == Some("UPDATE_AVAILABLE") && container.controller().is_some() {
+                    watcher.available.store(true, Ordering::Relaxed);
+                    ctx.request_repaint();
+                }
+            })
+        };
+        container.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        on_message.forget();
+
+        // The update may have been installed on an earlier visit and still be waiting.
+        {
+            let watcher = watcher.clone();
+            let ctx = ctx.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Some(registration) = web::registration(&container).await {
+                    if registration.waiting().is_some() && container.controller().is_some() {
+                        watcher.available.store(true, Ordering::Relaxed);
+                        ctx.request_repaint();
+                    }
+                }
+            });
+        }
+
+        watcher
+    }
+
+    /// Activates the waiting worker and reloads the page once it has taken over.
+    pub fn apply(&self) {
+        use wasm_bindgen::{closure::Closure, JsCast as _};
+
+        let Some(container) = web::service_worker_container() else {
+            web::reload();
+            return;
+        };
+        let on_controller_change = Closure::<dyn FnMut()>::new(web::reload);
+        container.set_oncontrollerchange(Some(on_controller_change.as_ref().unchecked_ref()));
+        on_controller_change.forget();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let waiting = web::registration(&container)
+                .await
+                .and_then(|registration| registration.waiting());
+            match waiting {
+                Some(worker) => {
+                    let message = js_sys::Object::new();
+                    js_sys::Reflect::set(&message, &"type".into(), &"SKIP_WAITING".into()).ok();
+                    if let Err(err) = worker.post_message(&message) {
+                        tracing::warn!("failed to message the service worker: {err:?}");
+                        web::reload();
+                    }
+                }
+                None => web::reload(),
+            }
+        });
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use wasm_bindgen::JsCast as _;
+
+    pub fn service_worker_container() -> Option<web_sys::ServiceWorkerContainer> {
+        let navigator = web_sys::window()?.navigator();
+        // Not available on insecure origins or when disabled by the browser.
+        js_sys::Reflect::has(&navigator, &"serviceWorker".into())
+            .unwrap_or(false)
+            .then(|| navigator.service_worker())
+    }
+
+    pub async fn registration(
+        container: &web_sys::ServiceWorkerContainer,
+    ) -> Option<web_sys::ServiceWorkerRegistration> {
+        let registration = wasm_bindgen_futures::JsFuture::from(container.get_registration())
+            .await
+            .ok()?;
+        registration.dyn_into().ok()
+    }
+
+    pub fn reload() {
+        if let Some(window) = web_sys::window() {
+            window.location().reload().ok();
+        }
+    }
+}
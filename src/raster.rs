@@ -0,0 +1,241 @@
+//! Paints egui output into an image on the CPU, without a GPU or window.
+//!
+//! Used for screenshots and for the snapshot tests of the theme. Blending happens in
+//! gamma space, like the glow and web backends do, so the result looks like the app.
+//!
+//! ```ignore
+//! let mut rasterizer = Rasterizer::default();
+//! rasterizer.update_textures(&output.textures_delta);
+//! let image = rasterizer.paint(&ctx.tessellate(output.shapes), pixels_per_point, [width, height]);
+//! ```
+
+use std::collections::HashMap;
+
+use egui::{epaint::Primitive, ClippedPrimitive, Color32, ColorImage, ImageData, Rect, TextureId};
+
+/// Keeps the textures egui asked for, and paints meshes using them.
+#[derive(Default)]
+pub struct Rasterizer {
+    textures: HashMap<TextureId, ColorImage>,
+}
+
+impl Rasterizer {
+    /// Applies the texture changes of a frame, e.g. the font atlas.
+    ///
+    /// Textures are only freed by the next call, since the frame they were freed in may
+    /// still use them.
+    pub fn update_textures(&mut self, delta: &egui::TexturesDelta) {
+        for (id, delta) in &delta.set {
+            let image = match &delta.image {
+                ImageData::Color(image) => image.clone(),
+                ImageData::Font(image) => ColorImage {
+                    size: image.size,
+                    pixels: image.srgba_pixels(None).collect(),
+                },
+            };
+            match delta.pos {
+                None => {
+                    self.textures.insert(*id, image);
+                }
+                Some([x, y]) => {
+                    let Some(texture) = self.textures.get_mut(id) else {
+                        tracing::warn!("partial update of unknown texture {id:?}");
+                        continue;
+                    };
+                    for row in 0..image.height() {
+                        let src = row * image.width();
+                        let dst = (y + row) * texture.width() + x;
+                        texture.pixels[dst..dst + image.width()]
+                            .copy_from_slice(&image.pixels[src..src + image.width()]);
+                    }
+                }
+            }
+        }
+        for id in &delta.free {
+            self.textures.remove(id);
+        }
+    }
+
+    /// Paints `primitives` into a transparent image of `size` pixels.
+    pub fn paint(
+        &self,
+        primitives: &[ClippedPrimitive],
+        pixels_per_point: f32,
+        size: [u32; 2],
+    ) -> image::RgbaImage {
+        let mut canvas = Canvas {
+            width: size[0] as usize,
+            height: size[1] as usize,
+            pixels: vec![[0.0; 4]; size[0] as usize * size[1] as usize],
+        };
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in primitives
+        {
+            let Primitive::Mesh(mesh) = primitive else {
+                continue; // Custom painting isn't supported.
+            };
+            let Some(texture) = self.textures.get(&mesh.texture_id) else {
+                tracing::warn!("missing texture {:?}", mesh.texture_id);
+                continue;
+            };
+            let clip_rect = Rect::from_min_max(
+                (clip_rect.min.to_vec2() * pixels_per_point).to_pos2(),
+                (clip_rect.max.to_vec2() * pixels_per_point).to_pos2(),
+            );
+            for triangle in mesh.indices.chunks_exact(3) {
+                let vertices = [0, 1, 2].map(|i| {
+                    let vertex = mesh.vertices[triangle[i] as usize];
+                    Vertex {
+                        pos: [
+                            vertex.pos.x * pixels_per_point,
+                            vertex.pos.y * pixels_per_point,
+                        ],
+                        uv: [vertex.uv.x, vertex.uv.y],
+                        color: rgba(vertex.color),
+                    }
+                });
+                canvas.triangle(&vertices, texture, clip_rect);
+            }
+        }
+        canvas.into_image()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Vertex {
+    pos: [f32; 2],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+/// Premultiplied gamma-space colors in `0..=1`.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    fn triangle(&mut self, vertices: &[Vertex; 3], texture: &ColorImage, clip_rect: Rect) {
+        let [a, b, c] = vertices.map(|v| v.pos);
+        let area = edge(a, b, c);
+        if area == 0.0 {
+            return;
+        }
+        // Walk the edges in the same direction, whichever way the triangle winds.
+        let (b, c, vertices) = if area < 0.0 {
+            (c, b, [vertices[0], vertices[2], vertices[1]])
+        } else {
+            (b, c, *vertices)
+        };
+        let area = area.abs();
+
+        let min_x = a[0].min(b[0]).min(c[0]).max(clip_rect.min.x).max(0.0);
+        let max_x = a[0]
+            .max(b[0])
+            .max(c[0])
+            .min(clip_rect.max.x)
+            .min(self.width as f32);
+        let min_y = a[1].min(b[1]).min(c[1]).max(clip_rect.min.y).max(0.0);
+        let max_y = a[1]
+            .max(b[1])
+            .max(c[1])
+            .min(clip_rect.max.y)
+            .min(self.height as f32);
+        if min_x >= max_x || min_y >= max_y {
+            return;
+        }
+
+        for y in (min_y.floor() as usize)..(max_y.ceil() as usize) {
+            for x in (min_x.floor() as usize)..(max_x.ceil() as usize) {
+                let p = [x as f32 + 0.5, y as f32 + 0.5];
+                if !clip_rect.contains(egui::pos2(p[0], p[1])) {
+                    continue;
+                }
+                let w = [edge(b, c, p), edge(c, a, p), edge(a, b, p)];
+                // Pixels exactly on an edge belong to only one of the triangles sharing it.
+                let inside = w
+                    .iter()
+                    .zip([(b, c), (c, a), (a, b)])
+                    .all(|(&w, (from, to))| w > 0.0 || (w == 0.0 && is_top_left(from, to)));
+                if !inside {
+                    continue;
+                }
+                let w = w.map(|w| w / area);
+                let lerp = |f: fn(&Vertex) -> f32| {
+                    w[0] * f(&vertices[0]) + w[1] * f(&vertices[1]) + w[2] * f(&vertices[2])
+                };
+                let uv = [lerp(|v| v.uv[0]), lerp(|v| v.uv[1])];
+                let texel = sample(texture, uv);
+                let color = [0, 1, 2, 3].map(|i| {
+                    (w[0] * vertices[0].color[i]
+                        + w[1] * vertices[1].color[i]
+                        + w[2] * vertices[2].color[i])
+                        * texel[i]
+                });
+
+                let dst = &mut self.pixels[y * self.width + x];
+                for i in 0..4 {
+                    dst[i] = color[i] + dst[i] * (1.0 - color[3]);
+                }
+            }
+        }
+    }
+
+    fn into_image(self) -> image::RgbaImage {
+        let mut image = image::RgbaImage::new(self.width as u32, self.height as u32);
+        for (dst, [r, g, b, a]) in image.pixels_mut().zip(self.pixels) {
+            // PNGs aren't premultiplied.
+            let unmultiply = |c: f32| {
+                if a > 0.0 {
+                    (c / a * 255.0).round().clamp(0.0, 255.0) as u8
+                } else {
+                    0
+                }
+            };
+            *dst = image::Rgba([
+                unmultiply(r),
+                unmultiply(g),
+                unmultiply(b),
+                (a * 255.0).round().clamp(0.0, 255.0) as u8,
+            ]);
+        }
+        image
+    }
+}
+
+/// Twice the signed area of the triangle `a b p`; positive if `p` is right of `a -> b`.
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+fn is_top_left(from: [f32; 2], to: [f32; 2]) -> bool {
+    let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+fn rgba(color: Color32) -> [f32; 4] {
+    color.to_array().map(|c| c as f32 / 255.0)
+}
+
+/// Bilinear sampling, clamped to the edges.
+fn sample(texture: &ColorImage, [u, v]: [f32; 2]) -> [f32; 4] {
+    let [width, height] = texture.size;
+    if width == 0 || height == 0 {
+        return [0.0; 4];
+    }
+    let x = (u * width as f32 - 0.5).clamp(0.0, (width - 1) as f32);
+    let y = (v * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+    let texel = |x: usize, y: usize| rgba(texture.pixels[y * width + x]);
+    let (p00, p10, p01, p11) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+    [0, 1, 2, 3].map(|i| {
+        let top = p00[i] + (p10[i] - p00[i]) * fx;
+        let bottom = p01[i] + (p11[i] - p01[i]) * fx;
+        top + (bottom - top) * fy
+    })
+}
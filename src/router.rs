@@ -0,0 +1,271 @@
+//! Maps paths like `/contract/secret1...` to what the app shows.
+//!
+//! On the web the path lives in the URL hash (`index.html#/contracts`), so links and the
+//! browser's back/forward buttons work. On native the initial route can be passed on the
+//! command line.
+
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Route {
+    #[default]
+    Home,
+    /// The contract explorer.
+    Contracts,
+    /// The contract explorer showing a single contract. Always a valid address, see
+    /// [`is_contract_address`], as it ends up in the path of LCD requests.
+    Contract(String),
+    /// The style settings window.
+    Settings,
+}
+
+impl Route {
+    /// Parses `/contracts`, `#/contracts` or `contracts`. Contract routes need a valid address.
+    pub fn parse(path: &str) -> Option<Self> {
+        let path = path.trim_start_matches('#');
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [] => Some(Route::Home),
+            ["contracts"] => Some(Route::Contracts),
+            ["contract", address] if is_contract_address(address) => {
+                Some(Route::Contract((*address).to_owned()))
+            }
+            ["settings"] => Some(Route::Settings),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Route::Home => write!(f, "/"),
+            Route::Contracts => write!(f, "/contracts"),
+            Route::Contract(address) => write!(f, "/contract/{address}"),
+            Route::Settings => write!(f, "/settings"),
+        }
+    }
+}
+
+impl std::str::FromStr for Route {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Self::parse(path).ok_or_else(|| format!("unknown route: {path}"))
+    }
+}
+
+/// Whether `address` is a Secret Network account or contract address: bech32 with the
+/// `secret` prefix, lowercase, and with a valid checksum.
+pub fn is_contract_address(address: &str) -> bool {
+    const CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    let Some(data) = address.strip_prefix("secret1") else {
+        return false;
+    };
+    // At least the checksum, 6 characters. Bech32 strings are at most 90 characters long.
+    if !(6..=90 - "secret1".len()).contains(&data.len()) {
+        return false;
+    }
+    let Some(data) = data
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&x| x == c)
+                .map(|value| value as u32)
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+
+    // The "expanded" prefix, then the data including the checksum.
+    let prefix = b"secret".iter().map(|c| u32::from(c >> 5));
+    let prefix = prefix
+        .chain([0])
+        .chain(b"secret".iter().map(|c| u32::from(c & 31)));
+    let polymod = prefix.chain(data).fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let mut checksum = (checksum & 0x1ff_ffff) << 5 ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if top >> i & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+        checksum
+    });
+    polymod == 1
+}
+
+/// Keeps the route and the app state in sync.
+///
+/// Each frame, apply [`Router::take_requested`] to the app state, then report the route
+/// of the resulting state with [`Router::navigated`].
+#[derive(Default)]
+pub struct Router {
+    current: Option<Route>,
+    requested: Arc<Mutex<Option<Route>>>,
+    /// The page was opened as `#dev...`, which `index.html` uses to skip the service worker.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    dev: bool,
+}
+
+impl Router {
+    /// Ask the app to show `route`, e.g. from the command line.
+    pub fn request(&mut self, route: Route) {
+        *self.requested.lock().unwrap() = Some(route);
+    }
+
+    /// The route the app should switch to, if it changed from the outside.
+    pub fn take_requested(&mut self) -> Option<Route> {
+        let route = self.requested.lock().unwrap().take()?;
+        (self.current.as_ref() != Some(&route)).then_some(route)
+    }
+
+    /// Tell the router which route the app is showing now.
+    pub fn navigated(&mut self, route: Route) {
+        if self.current.as_ref() == Some(&route) {
+            return;
+        }
+        #[cfg(target_arch = "wasm32")]
+        web::set_hash(&self.hash(&route), self.current.is_none());
+        self.current = Some(route);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Router {
+    pub fn new(_ctx: &egui::Context) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Router {
+    /// Starts out with the route in the page URL, and follows changes to it.
+    pub fn new(ctx: &egui::Context) -> Self {
+        use wasm_bindgen::{closure::Closure, JsCast as _};
+
+        let hash = web::hash();
+        let mut router = Self {
+            dev: hash.starts_with("#dev"),
+            ..Default::default()
+        };
+        if let Some(route) = web::parse_hash(&hash, router.dev) {
+            router.request(route);
+        }
+
+        let Some(window) = web_sys::window() else {
+            return router;
+        };
+        let on_hash_change = {
+            let requested = router.requested.clone();
+            let dev = router.dev;
+            let ctx = ctx.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                if let Some(route) = web::parse_hash(&web::hash(), dev) {
+                    *requested.lock().unwrap() = Some(route);
+                    ctx.request_repaint();
+                }
+            })
+        };
+        window
+            .add_event_listener_with_callback("hashchange", on_hash_change.as_ref().unchecked_ref())
+            .ok();
+        on_hash_change.forget();
+
+        router
+    }
+
+    fn hash(&self, route: &Route) -> String {
+        if self.dev {
+            format!("#dev{route}")
+        } else {
+            format!("#{route}")
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::Route;
+
+    pub fn parse_hash(hash: &str, dev: bool) -> Option<Route> {
+        Route::parse(if dev {
+            hash.trim_start_matches("#dev")
+        } else {
+            hash
+        })
+    }
+
+    pub fn hash() -> String {
+        web_sys::window()
+            .and_then(|window| window.location().hash().ok())
+            .unwrap_or_default()
+    }
+
+    /// Adds a history entry for `hash`, or replaces the current one.
+    pub fn set_hash(hash: &str, replace: bool) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        if hash == self::hash() {
+            return;
+        }
+        if replace {
+            if let Ok(history) = window.history() {
+                history
+                    .replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(hash))
+                    .ok();
+            }
+        } else {
+            window.location().set_hash(hash).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The sSCRT token contract on mainnet.
+    const ADDRESS: &str = "secret1k0jntykt7e4g3y88ltc60czgjuqdy4c9e8fzek";
+
+    #[test]
+    fn routes_round_trip() {
+        for route in [
+            Route::Home,
+            Route::Contracts,
+            Route::Contract(ADDRESS.to_owned()),
+            Route::Settings,
+        ] {
+            assert_eq!(Route::parse(&route.to_string()), Some(route.clone()));
+            assert_eq!(Route::parse(&format!("#{route}")), Some(route));
+        }
+        assert_eq!(Route::parse(""), Some(Route::Home));
+        assert_eq!(Route::parse("#/contracts/"), Some(Route::Contracts));
+        assert_eq!(Route::parse("#/portfolio"), None);
+    }
+
+    #[test]
+    fn contract_routes_need_a_valid_address() {
+        assert!(is_contract_address(ADDRESS));
+        for address in [
+            "..",
+            "secret1",
+            "secret1xyz",
+            // A typo, which the checksum catches.
+            "secret1k0jntykt7e4g3y88ltc60czgjuqdy4c9e8fzel",
+            "SECRET1K0JNTYKT7E4G3Y88LTC60CZGJUQDY4C9E8FZEK",
+            "cosmos1k0jntykt7e4g3y88ltc60czgjuqdy4c9e8fzek",
+            "secret1k0jntykt7e4g3y88ltc60czgjuqdy4c9e8fzek?x=1",
+        ] {
+            assert!(!is_contract_address(address), "{address}");
+            assert_eq!(Route::parse(&format!("#/contract/{address}")), None);
+        }
+        assert_eq!(Route::parse("#/contract/../../x"), None);
+        assert_eq!(Route::parse("#/contract/%2e%2e"), None);
+    }
+}
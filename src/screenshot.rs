@@ -0,0 +1,223 @@
+//! "Save screenshot": paints the current frame again on the CPU with [`crate::raster`],
+//! optionally at a higher scale or cropped to one window or panel, and saves it as a PNG.
+//!
+//! eframe doesn't give us the pixels it drew, so the app's UI is run once more on a
+//! headless context with the same style and memory (window positions, open headers, ...).
+
+use egui::{Button, ComboBox, Context, Rect, Slider, Ui};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::files::{save_file, Saved, Saving};
+use crate::i18n::Translator;
+use crate::raster::Rasterizer;
+
+/// A part of the app that can be captured on its own, e.g. ("Left panel", rect).
+///
+/// The name is in English, for `assets/tour.json`; [`region_name`] translates it.
+pub type Region = (&'static str, Rect);
+
+/// The message ids of the regions' names. The windows use their titles.
+const REGION_MESSAGES: [(&str, &str); 16] = [
+    ("Menu bar", "region-menu-bar"),
+    ("Bottom bar", "region-bottom-bar"),
+    ("Left panel", "region-left-panel"),
+    ("Right panel", "region-right-panel"),
+    ("Connect button", "region-connect-button"),
+    ("Style Settings", "style-settings"),
+    ("Contract Explorer", "contract-explorer"),
+    ("Fonts", "fonts"),
+    ("Accessibility", "accessibility"),
+    ("Links", "links"),
+    ("Log Console", "log-console"),
+    ("Contrast Report", "contrast-report"),
+    ("Icons", "icons"),
+    ("Window", "window"),
+    ("Alert", "alert"),
+    ("Help", "menu-help"),
+];
+
+/// The message id of the region called `name`, if it has one.
+pub fn region_message_id(name: &str) -> Option<&'static str> {
+    REGION_MESSAGES
+        .iter()
+        .find(|(region, _)| *region == name)
+        .map(|(_, id)| *id)
+}
+
+/// The name of the region called `name` in English, in the UI's language.
+pub fn region_name(t: &Translator, name: &str) -> String {
+    region_message_id(name).map_or_else(|| name.to_owned(), |id| t.get(id))
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Screenshot {
+    scale: f32,
+    /// `None` captures the whole app.
+    #[serde(skip)]
+    region: Option<&'static str>,
+    #[serde(skip)]
+    requested: bool,
+    /// The last screenshot, while the user picks where it goes.
+    #[serde(skip)]
+    saving: Option<Saving>,
+}
+
+impl Default for Screenshot {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            region: None,
+            requested: false,
+            saving: None,
+        }
+    }
+}
+
+/// What to capture, once the user clicked "Save".
+pub struct Request {
+    pub scale: f32,
+    pub region: Option<&'static str>,
+}
+
+impl Screenshot {
+    /// The settings and save button. Returns `true` when saving, so the window can close
+    /// before it's captured.
+    pub fn ui(&mut self, ui: &mut Ui, t: &Translator, regions: &[Region]) -> bool {
+        if let Some(region) = self.region {
+            if !regions.iter().any(|(name, _)| *name == region) {
+                self.region = None; // e.g. the window was closed
+            }
+        }
+
+        egui::Grid::new("screenshot_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.add(BidiLabel::new(t.get("screenshot-region")));
+                let selected = match self.region {
+                    Some(name) => region_name(t, name),
+                    None => t.get("screenshot-whole-app"),
+                };
+                ComboBox::from_id_source("screenshot_region")
+                    .selected_text(visual_order(&selected))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut self.region,
+                            None,
+                            visual_order(&t.get("screenshot-whole-app")),
+                        );
+                        for (name, _) in regions {
+                            ui.selectable_value(
+                                &mut self.region,
+                                Some(*name),
+                                visual_order(&region_name(t, name)),
+                            );
+                        }
+                    });
+                ui.end_row();
+
+                ui.add(BidiLabel::new(t.get("screenshot-scale")));
+                ui.add(Slider::new(&mut self.scale, 0.5..=4.0).suffix("×"));
+                ui.end_row();
+            });
+
+        ui.add_space(4.0);
+        // One at a time, while the dialog asking where to save it is open.
+        let save = Button::new(visual_order(&t.get("screenshot-save")));
+        self.requested = ui.add_enabled(self.saving.is_none(), save).clicked();
+        self.requested
+    }
+
+    pub fn take_request(&mut self) -> Option<Request> {
+        std::mem::take(&mut self.requested).then_some(Request {
+            scale: self.scale,
+            region: self.region,
+        })
+    }
+
+    /// Starts saving `image`, see [`save_file`]. Fails if it can't be encoded as PNG.
+    pub fn save(&mut self, ctx: &Context, image: &image::RgbaImage) -> image::ImageResult<()> {
+        let mut png = Vec::new();
+        image.write_to(
+            &mut std::io::Cursor::new(&mut png),
+            image::ImageOutputFormat::Png,
+        )?;
+        self.saving = Some(save_file(
+            ctx,
+            "screenshot.png",
+            ("PNG image", &["png"]),
+            png,
+        ));
+        Ok(())
+    }
+
+    /// Where the last screenshot was saved, once it was.
+    pub fn take_saved(&mut self) -> Option<Saved> {
+        let saved = self.saving.as_ref()?.take()?;
+        self.saving = None;
+        Some(saved)
+    }
+}
+
+/// Runs `run_ui` on a copy of `ctx` and paints it at `scale` times the current size.
+///
+/// `run_ui` should return the regions it laid out, so the image can be cropped to one.
+pub fn capture(
+    ctx: &Context,
+    request: &Request,
+    mut run_ui: impl FnMut(&Context) -> Vec<Region>,
+) -> Result<image::RgbaImage, String> {
+    let pixels_per_point = ctx.pixels_per_point() * request.scale;
+    let screen_rect = ctx.screen_rect();
+
+    let capture = Context::default();
+    capture.memory_mut(|memory| *memory = ctx.memory(|memory| memory.clone()));
+    capture.set_style(ctx.style());
+    capture.set_fonts(ctx.fonts(|fonts| fonts.lock().fonts.definitions().clone()));
+
+    let mut rasterizer = Rasterizer::default();
+    let mut output = Default::default();
+    let mut regions = Vec::new();
+    // The second frame lays out with the sizes measured in the first.
+    for _ in 0..2 {
+        let input = egui::RawInput {
+            screen_rect: Some(screen_rect),
+            pixels_per_point: Some(pixels_per_point),
+            ..Default::default()
+        };
+        output = capture.run(input, |ctx| regions = run_ui(ctx));
+        rasterizer.update_textures(&output.textures_delta);
+    }
+
+    let size = screen_rect.size() * pixels_per_point;
+    let image = rasterizer.paint(
+        &capture.tessellate(output.shapes),
+        pixels_per_point,
+        [size.x.round() as u32, size.y.round() as u32],
+    );
+
+    let Some(region) = request.region else {
+        return Ok(image);
+    };
+    let t = Translator::load(ctx);
+    let region_name = region_name(&t, region);
+    let region_arg = [("region", (&region_name).into())];
+    let (_, rect) = regions
+        .iter()
+        .find(|(name, _)| *name == region)
+        .ok_or_else(|| t.format("screenshot-region-hidden", &region_arg))?;
+    let rect = rect.intersect(screen_rect);
+    let min = (rect.min.to_vec2() * pixels_per_point).round();
+    let max = (rect.max.to_vec2() * pixels_per_point).round();
+    if max.x <= min.x || max.y <= min.y {
+        return Err(t.format("screenshot-region-off-screen", &region_arg));
+    }
+    Ok(image::imageops::crop_imm(
+        &image,
+        min.x as u32,
+        min.y as u32,
+        (max.x - min.x) as u32,
+        (max.y - min.y) as u32,
+    )
+    .to_image())
+}
@@ -0,0 +1,502 @@
+//! Key-value storage for the app's own data (cached chain data and the like).
+//!
+//! eframe's persistence is fine for the small `TemplateApp` state, but on the web it goes
+//! through local storage, which has a small quota and blocks on big writes.
+//! [`Storage`] instead writes to files on native and to IndexedDB on the web,
+//! and never blocks the UI.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageError {
+    /// The disk or the browser's storage quota is full.
+    QuotaExceeded,
+    Other(String),
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageError::QuotaExceeded => write!(f, "storage quota exceeded"),
+            StorageError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+pub type OnRead = Box<dyn FnOnce(Result<Option<Vec<u8>>, StorageError>) + Send>;
+pub type OnWrite = Box<dyn FnOnce(Result<(), StorageError>) + Send>;
+
+/// A storage backend. Operations complete asynchronously by calling `on_done`,
+/// possibly from another thread.
+pub trait Store: Send + Sync {
+    fn get(&self, key: &str, on_done: OnRead);
+
+    fn set(&self, key: &str, value: Vec<u8>, on_done: OnWrite);
+
+    fn remove(&self, key: &str, on_done: OnWrite);
+}
+
+/// The app's handle to its storage backend.
+///
+/// Failed operations are collected so the app can show them as notifications,
+/// see [`Self::take_errors`].
+#[derive(Clone)]
+pub struct Storage {
+    store: Arc<dyn Store>,
+    errors: Arc<Mutex<Vec<StorageError>>>,
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::new(MemoryStore::default())
+    }
+}
+
+impl Storage {
+    pub fn new(store: impl Store + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+            errors: Default::default(),
+        }
+    }
+
+    /// Files in the app's data directory on native, IndexedDB on the web.
+    pub fn platform_default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            match directories_next::ProjectDirs::from("", "", crate::APP_NAME) {
+                Some(dirs) => Self::new(FileStore::new(dirs.data_dir().join("data"))),
+                None => {
+                    tracing::warn!("no data directory found; app data will not be saved");
+                    Self::default()
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::new(IndexedDbStore)
+        }
+    }
+
+    /// Reads `key`. Errors are recorded and reported as a missing value.
+    pub fn get(&self, key: &str, on_done: impl FnOnce(Option<Vec<u8>>) + Send + 'static) {
+        let errors = self.errors.clone();
+        self.store.get(
+            key,
+            Box::new(move |result| match result {
+                Ok(value) => on_done(value),
+                Err(err) => {
+                    errors.lock().unwrap().push(err);
+                    on_done(None);
+                }
+            }),
+        );
+    }
+
+    pub fn set(&self, key: &str, value: Vec<u8>) {
+        self.store.set(key, value, self.record_error());
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.store.remove(key, self.record_error());
+    }
+
+    /// Errors from operations that have failed since the last call.
+    pub fn take_errors(&self) -> Vec<StorageError> {
+        std::mem::take(&mut *self.errors.lock().unwrap())
+    }
+
+    fn record_error(&self) -> OnWrite {
+        let errors = self.errors.clone();
+        Box::new(move |result| {
+            if let Err(err) = result {
+                tracing::warn!("storage: {err}");
+                errors.lock().unwrap().push(err);
+            }
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Keeps everything in memory. Used in tests, and as a fallback when there is nowhere to save.
+#[derive(Default)]
+pub struct MemoryStore {
+    values: Mutex<HashMap<String, Vec<u8>>>,
+    quota: Option<usize>,
+}
+
+impl MemoryStore {
+    /// Fails writes with [`StorageError::QuotaExceeded`] once the values would exceed `bytes`.
+    pub fn with_quota(bytes: usize) -> Self {
+        Self {
+            values: Default::default(),
+            quota: Some(bytes),
+        }
+    }
+}
+
+impl Store for MemoryStore {
+    fn get(&self, key: &str, on_done: OnRead) {
+        let value = self.values.lock().unwrap().get(key).cloned();
+        on_done(Ok(value));
+    }
+
+    fn set(&self, key: &str, value: Vec<u8>, on_done: OnWrite) {
+        let mut values = self.values.lock().unwrap();
+        if let Some(quota) = self.quota {
+            let used: usize = values
+                .iter()
+                .filter(|(existing, _)| *existing != key)
+                .map(|(_, value)| value.len())
+                .sum();
+            if used + value.len() > quota {
+                drop(values);
+                on_done(Err(StorageError::QuotaExceeded));
+                return;
+            }
+        }
+        values.insert(key.to_owned(), value);
+        drop(values);
+        on_done(Ok(()));
+    }
+
+    fn remove(&self, key: &str, on_done: OnWrite) {
+        self.values.lock().unwrap().remove(key);
+        on_done(Ok(()));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// One file per key in a directory. Reads and writes happen on a background thread, one
+/// at a time in the order they were asked for, so a read sees the writes before it.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStore {
+    root: std::path::PathBuf,
+    /// The worker thread's queue. It stops once this is dropped.
+    tasks: Mutex<std::sync::mpsc::Sender<Box<dyn FnOnce() + Send>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStore {
+    pub fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        let (tasks, receiver) = std::sync::mpsc::channel::<Box<dyn FnOnce() + Send>>();
+        std::thread::Builder::new()
+            .name("storage".to_owned())
+            .spawn(move || {
+                for task in receiver {
+                    task();
+                }
+            })
+            .expect("failed to spawn storage thread");
+        Self {
+            root: root.into(),
+            tasks: Mutex::new(tasks),
+        }
+    }
+
+    /// Keys may contain `/` to group values into directories; anything else
+    /// that isn't safe in a file name is escaped.
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        let mut path = self.root.clone();
+        for part in key.split('/') {
+            let part: String = part
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c.to_string(),
+                    c => format!("%{:x}", c as u32),
+                })
+                .collect();
+            path.push(part.replace("..", "%2e%2e"));
+        }
+        path
+    }
+
+    /// A temporary file next to `path` for one write, e.g. `codes.json.1234.5.tmp` for the
+    /// fifth write of process 1234.
+    fn temp_path(path: &std::path::Path) -> std::path::PathBuf {
+        static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let count = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let mut name = path.file_name().unwrap_or_default().to_owned();
+        name.push(format!(".{}.{count}.tmp", std::process::id()));
+        path.with_file_name(name)
+    }
+
+    /// Queues `task` behind the operations before it.
+    fn spawn(&self, task: impl FnOnce() + Send + 'static) {
+        // The worker only stops when the store is dropped.
+        self.tasks.lock().unwrap().send(Box::new(task)).ok();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn io_error(err: std::io::Error) -> StorageError {
+    // ENOSPC/EDQUOT (linux), ERROR_HANDLE_DISK_FULL/ERROR_DISK_FULL (windows).
+    let disk_full = if cfg!(windows) {
+        matches!(err.raw_os_error(), Some(39 | 112))
+    } else {
+        matches!(err.raw_os_error(), Some(28 | 122))
+    };
+    if disk_full {
+        StorageError::QuotaExceeded
+    } else {
+        StorageError::Other(err.to_string())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Store for FileStore {
+    fn get(&self, key: &str, on_done: OnRead) {
+        let path = self.path(key);
+        self.spawn(move || {
+            on_done(match std::fs::read(&path) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(io_error(err)),
+            });
+        });
+    }
+
+    fn set(&self, key: &str, value: Vec<u8>, on_done: OnWrite) {
+        let path = self.path(key);
+        self.spawn(move || {
+            // Write to a temporary file first so a full disk can't leave a truncated value behind.
+            // Named uniquely, so it neither is another key's file nor shared by two writes.
+            let tmp = Self::temp_path(&path);
+            let result = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| std::fs::write(&tmp, &value))
+                .and_then(|()| std::fs::rename(&tmp, &path));
+            if result.is_err() {
+                std::fs::remove_file(&tmp).ok();
+            }
+            on_done(result.map_err(io_error));
+        });
+    }
+
+    fn remove(&self, key: &str, on_done: OnWrite) {
+        let path = self.path(key);
+        self.spawn(move || {
+            on_done(match std::fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(io_error(err)),
+                _ => Ok(()),
+            });
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// An IndexedDB object store, which has a far larger quota than local storage.
+#[cfg(target_arch = "wasm32")]
+pub struct IndexedDbStore;
+
+#[cfg(target_arch = "wasm32")]
+impl Store for IndexedDbStore {
+    fn get(&self, key: &str, on_done: OnRead) {
+        let key = key.to_owned();
+        wasm_bindgen_futures::spawn_local(async move {
+            on_done(indexed_db::get(&key).await);
+        });
+    }
+
+    fn set(&self, key: &str, value: Vec<u8>, on_done: OnWrite) {
+        let key = key.to_owned();
+        wasm_bindgen_futures::spawn_local(async move {
+            on_done(indexed_db::set(&key, Some(&value)).await);
+        });
+    }
+
+    fn remove(&self, key: &str, on_done: OnWrite) {
+        let key = key.to_owned();
+        wasm_bindgen_futures::spawn_local(async move {
+            on_done(indexed_db::set(&key, None).await);
+        });
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod indexed_db {
+    use wasm_bindgen::{closure::Closure, JsCast as _, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{IdbDatabase, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+    use super::StorageError;
+
+    const DATABASE: &str = "eframe_template";
+    const OBJECT_STORE: &str = "data";
+
+    fn js_error(value: JsValue) -> StorageError {
+        match value.dyn_ref::<web_sys::DomException>() {
+            Some(exception) if exception.name() == "QuotaExceededError" => {
+                StorageError::QuotaExceeded
+            }
+            Some(exception) => StorageError::Other(exception.message()),
+            None => StorageError::Other(format!("{value:?}")),
+        }
+    }
+
+    /// Resolves once `request` succeeds, or fails with its error.
+    async fn request(request: &IdbRequest) -> Result<JsValue, StorageError> {
+        let promise = js_sys::Promise::new(&mut |resolve, reject| {
+            let succeeded = request.clone();
+            let on_success = Closure::once_into_js(move || {
+                resolve
+                    .call1(&JsValue::NULL, &succeeded.result().unwrap_or_default())
+                    .ok();
+            });
+            let failed = request.clone();
+            let on_error = Closure::once_into_js(move || {
+                let error = failed.error().ok().flatten().map(JsValue::from);
+                reject
+                    .call1(&JsValue::NULL, &error.unwrap_or_default())
+                    .ok();
+            });
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+        });
+        JsFuture::from(promise).await.map_err(js_error)
+    }
+
+    /// Resolves once `transaction` is committed. Quota errors show up here rather than
+    /// on the individual request.
+    async fn committed(transaction: &IdbTransaction) -> Result<(), StorageError> {
+        let promise = js_sys::Promise::new(&mut |resolve, reject| {
+            let on_complete = Closure::once_into_js(move || {
+                resolve.call0(&JsValue::NULL).ok();
+            });
+            let failed = transaction.clone();
+            let on_abort = Closure::once_into_js(move || {
+                let error = failed.error().map(JsValue::from);
+                reject
+                    .call1(&JsValue::NULL, &error.unwrap_or_default())
+                    .ok();
+            });
+            transaction.set_oncomplete(Some(on_complete.unchecked_ref()));
+            transaction.set_onabort(Some(on_abort.unchecked_ref()));
+        });
+        JsFuture::from(promise).await.map(|_| ()).map_err(js_error)
+    }
+
+    async fn open() -> Result<IdbDatabase, StorageError> {
+        let factory = web_sys::window()
+            .and_then(|window| window.indexed_db().ok().flatten())
+            .ok_or_else(|| StorageError::Other("IndexedDB is not available".to_owned()))?;
+        let open = factory.open_with_u32(DATABASE, 1).map_err(js_error)?;
+
+        let upgrading = open.clone();
+        let on_upgrade = Closure::once_into_js(move || {
+            if let Ok(database) = upgrading.result() {
+                let database: IdbDatabase = database.unchecked_into();
+                database.create_object_store(OBJECT_STORE).ok();
+            }
+        });
+        open.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+        Ok(request(&open).await?.unchecked_into())
+    }
+
+    pub async fn get(key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let database = open().await?;
+        let store = database
+            .transaction_with_str(OBJECT_STORE)
+            .and_then(|transaction| transaction.object_store(OBJECT_STORE))
+            .map_err(js_error)?;
+        let value = request(&store.get(&key.into()).map_err(js_error)?).await?;
+        database.close();
+        Ok((!value.is_undefined()).then(|| js_sys::Uint8Array::new(&value).to_vec()))
+    }
+
+    /// Writes `value` to `key`, or deletes `key` if `value` is `None`.
+    pub async fn set(key: &str, value: Option<&[u8]>) -> Result<(), StorageError> {
+        let database = open().await?;
+        let transaction = database
+            .transaction_with_str_and_mode(OBJECT_STORE, IdbTransactionMode::Readwrite)
+            .map_err(js_error)?;
+        let store = transaction.object_store(OBJECT_STORE).map_err(js_error)?;
+        match value {
+            Some(value) => store.put_with_key(&js_sys::Uint8Array::from(value), &key.into()),
+            None => store.delete(&key.into()),
+        }
+        .map_err(js_error)?;
+        let result = committed(&transaction).await;
+        database.close();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_for_get(storage: &Storage, key: &str) -> Option<Vec<u8>> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        storage.get(key, move |value| sender.send(value).unwrap());
+        receiver.recv().unwrap()
+    }
+
+    #[test]
+    fn memory_store_reports_quota_errors() {
+        let storage = Storage::new(MemoryStore::with_quota(8));
+        storage.set("a", vec![0; 6]);
+        storage.set("b", vec![0; 6]);
+        assert_eq!(storage.take_errors(), vec![StorageError::QuotaExceeded]);
+        assert_eq!(wait_for_get(&storage, "a"), Some(vec![0; 6]));
+        assert_eq!(wait_for_get(&storage, "b"), None);
+
+        // Overwriting a value only counts the new size.
+        storage.set("a", vec![1; 8]);
+        assert!(storage.take_errors().is_empty());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn file_store_round_trip() {
+        let root = std::env::temp_dir().join(format!("storage-test-{}", std::process::id()));
+        let storage = Storage::new(FileStore::new(&root));
+
+        storage.set("contracts/secret-4/../codes.json", b"[1, 2]".to_vec());
+        // The read is queued after the write.
+        assert_eq!(
+            wait_for_get(&storage, "contracts/secret-4/../codes.json").as_deref(),
+            Some(&b"[1, 2]"[..])
+        );
+        assert!(
+            root.join("contracts/secret-4").is_dir(),
+            "`..` must not escape"
+        );
+
+        storage.remove("contracts/secret-4/../codes.json");
+        assert_eq!(
+            wait_for_get(&storage, "contracts/secret-4/../codes.json"),
+            None
+        );
+        assert!(storage.take_errors().is_empty());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn file_store_writes_in_order_with_their_own_temp_files() {
+        let root = std::env::temp_dir().join(format!("storage-tmp-test-{}", std::process::id()));
+        let storage = Storage::new(FileStore::new(&root));
+
+        // A key ending in `.tmp`, and quick writes to another key.
+        storage.set("codes.tmp", b"tmp".to_vec());
+        for i in 0..20u8 {
+            storage.set("codes.json", vec![i]);
+        }
+        // The last write wins, and the read waits for it.
+        assert_eq!(wait_for_get(&storage, "codes.json"), Some(vec![19]));
+        assert_eq!(wait_for_get(&storage, "codes.tmp"), Some(b"tmp".to_vec()));
+        let files = std::fs::read_dir(&root).map_or(0, |dir| dir.count());
+        assert_eq!(files, 2, "temporary files were left behind");
+        assert!(storage.take_errors().is_empty());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -1,38 +1,45 @@
+use std::collections::BTreeMap;
+
 use egui::style::{
     Interaction, Margin, Selection, Spacing, Style, TextStyle, Visuals, WidgetVisuals, Widgets,
 };
-use egui::{vec2, Color32, FontData, FontDefinitions, FontFamily, FontId, FontTweak, Rounding};
+use egui::{vec2, Color32, FontDefinitions, FontFamily, FontId, FontTweak, Rounding};
 use epaint::{
     FontFamily::{Monospace, Proportional},
     Shadow, Stroke,
 };
 
-#[inline]
-pub fn custom_style_1() -> TextStyle {
-    TextStyle::Name("CustomStyle1".into())
-}
+use crate::background::Background;
+use crate::fonts::FontConfig;
+use crate::text_styles::{AppTextStyle, TextStyleDef, TextStyles};
 
-#[inline]
-pub fn custom_style_2() -> TextStyle {
-    TextStyle::Name("CustomStyle2".into())
+/// The font family for bold text, see [`my_font_config`].
+pub const BOLD_FAMILY: &str = "fira_sans_bold";
+
+/// The app's own text styles, see [`crate::text_styles`].
+pub fn my_text_styles() -> TextStyles {
+    TextStyles::from([
+        (
+            AppTextStyle::Title,
+            TextStyleDef::new(22.0, Proportional).bold(),
+        ),
+        (
+            AppTextStyle::Subtitle,
+            TextStyleDef::new(22.0, Proportional),
+        ),
+        (AppTextStyle::MonoSmall, TextStyleDef::new(11.0, Monospace)),
+        (AppTextStyle::Numeric, TextStyleDef::new(14.0, Monospace)),
+    ])
 }
 
 pub fn my_style() -> Style {
-    Style {
+    let mut style = Style {
         override_font_id: None,
         override_text_style: None,
         text_styles: [
             (
                 TextStyle::Heading,
-                FontId::new(18.0, FontFamily::Name("fira_sans_bold".into())),
-            ),
-            (
-                TextStyle::Name("CustomStyle1".into()),
-                FontId::new(22.0, FontFamily::Name("fira_sans_bold".into())),
-            ),
-            (
-                TextStyle::Name("CustomStyle2".into()),
-                FontId::new(22.0, Proportional),
+                FontId::new(18.0, FontFamily::Name(BOLD_FAMILY.into())),
             ),
             (TextStyle::Body, FontId::new(14.0, Proportional)),
             (TextStyle::Monospace, FontId::new(14.0, Monospace)),
@@ -51,7 +58,9 @@ pub fn my_style() -> Style {
         animation_time: 1.0 / 12.0,
         debug: Default::default(),
         explanation_tooltips: false,
-    }
+    };
+    my_text_styles().apply_to(&mut style);
+    style
 }
 
 pub fn my_spacing() -> Spacing {
@@ -134,7 +143,7 @@ pub fn my_dark_visuals() -> Visuals {
         extreme_bg_color: Color32::from_gray(10),            // e.g. TextEdit background
         code_bg_color: Color32::from_gray(64),
         warn_fg_color: Color32::from_rgb(255, 143, 0), // orange
-        error_fg_color: Color32::from_rgb(255, 0, 0),  // red
+        error_fg_color: Color32::from_rgb(255, 80, 80), // red, light enough to read on the panels
 
         window_rounding: Rounding::same(0.0),
         window_shadow: Shadow {
@@ -166,60 +175,131 @@ pub fn my_dark_visuals() -> Visuals {
     }
 }
 
+/// [`my_dark_visuals`] with darker backgrounds and brighter text, see
+/// [`crate::contrast::high_contrast`].
+pub fn my_high_contrast_visuals() -> Visuals {
+    let mut visuals = crate::contrast::high_contrast(&my_dark_visuals());
+    visuals.text_cursor_width = 3.0;
+    visuals
+}
+
+/// Which fonts each family uses, most preferred first, and how each font is adjusted.
+/// The fonts themselves are in [`crate::fonts::bundled_fonts`].
+pub fn my_font_config() -> FontConfig {
+    let tweak = FontTweak {
+        scale: 1.0,
+        y_offset_factor: -0.2,
+        y_offset: 0.0,
+    };
+    // egui's emoji fonts come with their own tweaks.
+    let egui_defaults = FontDefinitions::default().font_data;
+    let mut tweaks: BTreeMap<String, FontTweak> = egui_defaults
+        .iter()
+        .map(|(name, font)| (name.clone(), font.tweak))
+        .collect();
+    tweaks.insert("open_sans".to_owned(), tweak);
+    tweaks.insert("fira_sans_bold".to_owned(), tweak);
+
+    let fonts = |names: &[&str]| -> Vec<String> {
+        names
+            .iter()
+            .copied()
+            // Only there with the `cjk` feature, see `fonts/README.md`.
+            .chain([crate::fonts::CJK_FONT])
+            .map(str::to_owned)
+            .collect()
+    };
+    FontConfig {
+        families: vec![
+            (
+                Proportional,
+                fonts(&[
+                    "open_sans",
+                    "Ubuntu-Light",
+                    "NotoEmoji-Regular",
+                    "emoji-icon-font",
+                ]),
+            ),
+            (
+                Monospace,
+                fonts(&[
+                    "Hack",
+                    "Ubuntu-Light",
+                    "NotoEmoji-Regular",
+                    "emoji-icon-font",
+                    "open_sans",
+                ]),
+            ),
+            (
+                FontFamily::Name(BOLD_FAMILY.into()),
+                fonts(&["fira_sans_bold", "open_sans", "NotoEmoji-Regular"]),
+            ),
+        ],
+        tweaks,
+    }
+}
+
 pub fn my_font_definitions() -> FontDefinitions {
-    // Start with the default fonts (we will be adding to them rather than replacing them).
-    let mut fonts = FontDefinitions::default();
-
-    // Install my own font (maybe supporting non-latin characters):
-    fonts.font_data.insert(
-        "open_sans".to_owned(),
-        FontData::from_static(include_bytes!("../fonts/OpenSans-Regular.ttf")).tweak(FontTweak {
-            scale: 1.0,
-            y_offset_factor: -0.2,
-            y_offset: 0.0,
-        }),
-    ); // .ttf and .otf supported
-
-    // Put my font first (highest priority):
-    fonts
-        .families
-        .get_mut(&FontFamily::Proportional)
-        .unwrap()
-        .insert(0, "open_sans".to_owned());
-
-    // Put my font as last fallback for monospace:
-    fonts
-        .families
-        .get_mut(&FontFamily::Monospace)
-        .unwrap()
-        .push("open_sans".to_owned());
-
-    // Install another font
-    fonts.font_data.insert(
-        "fira_sans_bold".to_owned(),
-        FontData::from_static(include_bytes!("../fonts/FiraSans-Bold.ttf")).tweak(FontTweak {
-            scale: 1.0,
-            y_offset_factor: -0.2,
-            y_offset: 0.0,
-        }),
-    );
-    fonts.families.insert(
-        FontFamily::Name("fira_sans_bold".into()),
-        vec!["fira_sans_bold".to_owned()],
-    );
-
-    fonts
+    crate::fonts::definitions(&my_font_config(), crate::fonts::bundled_fonts())
 }
 
 pub fn reset_animation_time(ctx: &egui::Context) {
-    let mut style: egui::Style = (*ctx.style()).clone();
     // 1.0 / 12.0 is the default
-    style.animation_time = 1.0 / 12.0;
-    ctx.set_style(style);
+    change_animation_time(ctx, 1.0 / 12.0);
 }
 
+/// Stays at 0 with the reduced motion setting, see [`crate::accessibility`].
 pub fn change_animation_time(ctx: &egui::Context, time: f32) {
     let mut style: egui::Style = (*ctx.style()).clone();
-    style.animation_time = time;
+    style.animation_time = if crate::accessibility::reduced_motion(ctx) {
+        0.0
+    } else {
+        time
+    };
     ctx.set_style(style);
 }
+
+/// What is painted behind the central panel, see [`crate::background`].
+pub fn my_background(visuals: &Visuals) -> Background {
+    let color = if visuals.dark_mode {
+        Color32::from_white_alpha(6)
+    } else {
+        Color32::from_black_alpha(12)
+    };
+    Background::Pattern {
+        color,
+        spacing: 24.0,
+        radius: 1.0,
+        speed: 3.0,
+    }
+}
+
+/// Colors used to syntax-highlight JSON and other structured data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SyntaxColors {
+    pub key: Color32,
+    pub string: Color32,
+    pub number: Color32,
+    pub literal: Color32, // true, false, null
+    pub punctuation: Color32,
+}
+
+pub fn my_syntax_colors(visuals: &Visuals) -> SyntaxColors {
+    if visuals.dark_mode {
+        SyntaxColors {
+            key: Color32::from_rgb(156, 220, 254),
+            string: Color32::from_rgb(206, 145, 120),
+            number: Color32::from_rgb(181, 206, 168),
+            literal: Color32::from_rgb(86, 156, 214),
+            punctuation: Color32::from_gray(140),
+        }
+    } else {
+        SyntaxColors {
+            key: Color32::from_rgb(0, 16, 128),
+            string: Color32::from_rgb(163, 21, 21),
+            number: Color32::from_rgb(9, 134, 88),
+            literal: Color32::from_rgb(0, 0, 255),
+            punctuation: Color32::from_gray(100),
+        }
+    }
+}
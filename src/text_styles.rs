@@ -0,0 +1,218 @@
+//! Named text styles, on top of egui's built-in ones (`Body`, `Heading`, ...).
+//!
+//! The theme declares them in [`crate::style::my_text_styles`], with a font and optionally
+//! a color. Use them through [`AppTextStyle`], so a typo is a compile error:
+//!
+//! ```ignore
+//! ui.label(RichText::new("Contracts").app_style(ui, AppTextStyle::Title));
+//! ```
+//!
+//! A style that the theme doesn't define falls back to `Body`, with a warning in the log,
+//! instead of egui's panic for unknown text styles.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use egui::{Color32, FontFamily, FontId, RichText, Style, TextStyle, Ui};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AppTextStyle {
+    /// Big and bold, e.g. the top of a page.
+    Title,
+    Subtitle,
+    /// Small monospace, e.g. for hashes.
+    MonoSmall,
+    /// Ids, amounts and other numbers.
+    Numeric,
+}
+
+impl AppTextStyle {
+    pub const ALL: [AppTextStyle; 4] = [
+        AppTextStyle::Title,
+        AppTextStyle::Subtitle,
+        AppTextStyle::MonoSmall,
+        AppTextStyle::Numeric,
+    ];
+
+    /// The name in `Style::text_styles`.
+    pub fn name(self) -> &'static str {
+        match self {
+            AppTextStyle::Title => "Title",
+            AppTextStyle::Subtitle => "Subtitle",
+            AppTextStyle::MonoSmall => "Mono-small",
+            AppTextStyle::Numeric => "Numeric",
+        }
+    }
+
+    pub fn text_style(self) -> TextStyle {
+        TextStyle::Name(self.name().into())
+    }
+
+    /// The font of this style in `ui`, or the `Body` font if the theme doesn't have it.
+    pub fn font_id(self, ui: &Ui) -> FontId {
+        if let Some(font_id) = ui.style().text_styles.get(&self.text_style()) {
+            return font_id.clone();
+        }
+        if let Some(definition) = TextStyles::load(ui.ctx()).get(self) {
+            return definition.font_id();
+        }
+        warn_missing(ui.ctx(), self);
+        TextStyle::Body.resolve(ui.style())
+    }
+}
+
+/// How a named text style looks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextStyleDef {
+    pub size: f32,
+    pub family: FontFamily,
+    /// Use the theme's bold font instead of `family`.
+    pub bold: bool,
+    /// Overrides the text color of the visuals.
+    pub color: Option<Color32>,
+}
+
+impl TextStyleDef {
+    pub fn new(size: f32, family: FontFamily) -> Self {
+        Self {
+            size,
+            family,
+            bold: false,
+            color: None,
+        }
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn font_id(&self) -> FontId {
+        if self.bold {
+            FontId::new(
+                self.size,
+                FontFamily::Name(crate::style::BOLD_FAMILY.into()),
+            )
+        } else {
+            FontId::new(self.size, self.family.clone())
+        }
+    }
+}
+
+/// The theme's named text styles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextStyles(BTreeMap<AppTextStyle, TextStyleDef>);
+
+impl<const N: usize> From<[(AppTextStyle, TextStyleDef); N]> for TextStyles {
+    fn from(styles: [(AppTextStyle, TextStyleDef); N]) -> Self {
+        Self(styles.into())
+    }
+}
+
+impl TextStyles {
+    pub fn get(&self, style: AppTextStyle) -> Option<&TextStyleDef> {
+        self.0.get(&style)
+    }
+
+    pub fn insert(&mut self, style: AppTextStyle, definition: TextStyleDef) {
+        self.0.insert(style, definition);
+    }
+
+    /// Adds the fonts to `style.text_styles`, so plain egui can use the styles too.
+    /// Fonts already there, e.g. from a `--theme` file, are kept.
+    pub fn apply_to(&self, style: &mut Style) {
+        for (name, definition) in &self.0 {
+            style
+                .text_styles
+                .entry(name.text_style())
+                .or_insert_with(|| definition.font_id());
+        }
+    }
+
+    /// Makes these the styles used by [`RichTextExt::app_style`] in `ctx`.
+    pub fn install(self, ctx: &egui::Context) {
+        let mut style = (*ctx.style()).clone();
+        self.apply_to(&mut style);
+        ctx.set_style(style);
+        ctx.data_mut(|data| data.insert_temp(registry_id(), Arc::new(self)));
+    }
+
+    pub fn load(ctx: &egui::Context) -> Arc<Self> {
+        ctx.data_mut(|data| data.get_temp(registry_id()))
+            .unwrap_or_default()
+    }
+}
+
+fn registry_id() -> egui::Id {
+    egui::Id::new("text_styles")
+}
+
+/// Logs each missing style once per context.
+fn warn_missing(ctx: &egui::Context, style: AppTextStyle) {
+    let id = egui::Id::new("missing_text_styles");
+    let first_time = ctx.data_mut(|data| {
+        let warned: &mut Vec<AppTextStyle> = data.get_temp_mut_or_default(id);
+        let first_time = !warned.contains(&style);
+        if first_time {
+            warned.push(style);
+        }
+        first_time
+    });
+    if first_time {
+        tracing::warn!(
+            "the theme has no {:?} text style, using Body instead",
+            style.name()
+        );
+    }
+}
+
+pub trait RichTextExt {
+    /// Sets the font and color of a named text style.
+    fn app_style(self, ui: &Ui, style: AppTextStyle) -> Self;
+}
+
+impl RichTextExt for RichText {
+    fn app_style(self, ui: &Ui, style: AppTextStyle) -> Self {
+        let text = self.font(style.font_id(ui));
+        match TextStyles::load(ui.ctx())
+            .get(style)
+            .and_then(|def| def.color)
+        {
+            Some(color) => text.color(color),
+            None => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_styles_fall_back_to_body() {
+        let ctx = egui::Context::default();
+        TextStyles::from([(
+            AppTextStyle::Title,
+            TextStyleDef::new(30.0, FontFamily::Proportional).color(Color32::RED),
+        )])
+        .install(&ctx);
+
+        let mut title = None;
+        let mut numeric = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                title = Some(AppTextStyle::Title.font_id(ui));
+                numeric = Some(AppTextStyle::Numeric.font_id(ui));
+                // Would panic with `.text_style(AppTextStyle::Numeric.text_style())`.
+                ui.label(RichText::new("42").app_style(ui, AppTextStyle::Numeric));
+            });
+        });
+        assert_eq!(title.unwrap().size, 30.0);
+        assert_eq!(numeric.unwrap(), TextStyle::Body.resolve(&ctx.style()),);
+    }
+}
@@ -0,0 +1,225 @@
+//! The tour shown on the first start: a few steps, each pointing out a part of the app with
+//! a spotlight and explaining it.
+//!
+//! The steps are in `assets/tour.json`. Each names the region it points at (see
+//! [`crate::screenshot::Region`]), or none for a step in the middle of the screen, and the
+//! message ids of its title and text in the catalogs in `locales/`. The text is markdown.
+
+use egui::{Align2, Area, Color32, Frame, Id, Key, Order, Rect, RichText, Sense, Stroke};
+
+use crate::direction::{visual_order, BidiLabel};
+use crate::i18n::Translator;
+use crate::markdown::Markdown;
+use crate::screenshot::Region;
+use crate::text_styles::{AppTextStyle, RichTextExt};
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+struct Step {
+    target: Option<String>,
+    title: String,
+    text: String,
+}
+
+#[derive(serde::Deserialize)]
+struct TourFile {
+    steps: Vec<Step>,
+}
+
+fn load_steps() -> Vec<Step> {
+    serde_json::from_str::<TourFile>(include_str!("../assets/tour.json"))
+        .map(|file| file.steps)
+        .unwrap_or_else(|err| {
+            tracing::warn!("assets/tour.json: {err}");
+            Vec::new()
+        })
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Tour {
+    /// Set with the checkbox in the tour, or by finishing it.
+    pub dont_show_again: bool,
+    #[serde(skip)]
+    steps: Vec<Step>,
+    /// The step shown, while the tour is open.
+    #[serde(skip)]
+    step: Option<usize>,
+    #[serde(skip)]
+    started: bool,
+}
+
+impl Tour {
+    /// Opens the tour at the first step.
+    pub fn start(&mut self) {
+        self.started = true;
+        self.steps = load_steps();
+        self.step = (!self.steps.is_empty()).then_some(0);
+    }
+
+    /// Starts the tour on the first frame, unless it isn't wanted. Escape closes it.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        if !self.started && !self.dont_show_again {
+            self.start();
+        }
+        self.started = true;
+        if self.step.is_some()
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, Key::Escape))
+        {
+            self.step = None;
+        }
+    }
+
+    /// Shows the current step over everything else, with a spotlight on its region in
+    /// `regions`.
+    pub fn ui(&mut self, ctx: &egui::Context, t: &Translator, regions: &[Region]) {
+        let Some(index) = self.step else {
+            return;
+        };
+        let step = &self.steps[index];
+        let target = step
+            .target
+            .as_deref()
+            .and_then(|target| regions.iter().find(|(name, _)| *name == target))
+            .map(|(_, rect)| *rect);
+
+        let screen = ctx.screen_rect();
+        show_spotlight(ctx, screen, target);
+
+        // Next to the target, on the side with more room.
+        let (pivot, pos) = match target {
+            Some(target) => {
+                let x = if target.center().x > screen.center().x {
+                    (egui::Align::Max, target.right())
+                } else {
+                    (egui::Align::Min, target.left())
+                };
+                let y = if target.center().y > screen.center().y {
+                    (egui::Align::Max, target.top() - 12.0)
+                } else {
+                    (egui::Align::Min, target.bottom() + 12.0)
+                };
+                (Align2([x.0, y.0]), egui::pos2(x.1, y.1))
+            }
+            None => (Align2::CENTER_CENTER, screen.center()),
+        };
+
+        let count = self.steps.len();
+        let mut next = None;
+        let response = Area::new("tour")
+            .order(Order::Foreground)
+            .pivot(pivot)
+            .fixed_pos(pos)
+            .constrain(true)
+            .drag_bounds(screen)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(320.0);
+                    let title = RichText::new(t.get(&step.title));
+                    ui.add(BidiLabel::new(title.app_style(ui, AppTextStyle::Title)));
+                    ui.add_space(4.0);
+                    Markdown::new(("tour", index), &t.get(&step.text)).show(ui);
+                    ui.add_space(8.0);
+                    ui.checkbox(
+                        &mut self.dont_show_again,
+                        visual_order(&t.get("tour-dont-show")),
+                    );
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button(visual_order(&t.get("tour-skip"))).clicked() {
+                            next = Some(None);
+                        }
+                        let progress = t.format(
+                            "tour-progress",
+                            &[("step", (index + 1).into()), ("count", count.into())],
+                        );
+                        ui.add(BidiLabel::new(RichText::new(progress).weak()));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if index + 1 == count {
+                                if ui.button(visual_order(&t.get("tour-done"))).clicked() {
+                                    self.dont_show_again = true;
+                                    next = Some(None);
+                                }
+                            } else if ui.button(visual_order(&t.get("tour-next"))).clicked() {
+                                next = Some(Some(index + 1));
+                            }
+                            if index > 0 && ui.button(visual_order(&t.get("tour-back"))).clicked() {
+                                next = Some(Some(index - 1));
+                            }
+                        });
+                    });
+                });
+            });
+        ctx.move_to_top(response.response.layer_id);
+        if let Some(next) = next {
+            self.step = next;
+        }
+    }
+}
+
+/// Dims the screen, except for `target`, and keeps the clicks off what is dimmed.
+fn show_spotlight(ctx: &egui::Context, screen: Rect, target: Option<Rect>) {
+    let dim = Color32::from_black_alpha(160);
+    let hole = target.map(|target| target.expand(4.0).intersect(screen));
+    let dimmed = match hole {
+        Some(hole) => vec![
+            Rect::from_min_max(screen.min, egui::pos2(screen.max.x, hole.min.y)),
+            Rect::from_min_max(egui::pos2(screen.min.x, hole.max.y), screen.max),
+            Rect::from_min_max(
+                egui::pos2(screen.min.x, hole.min.y),
+                egui::pos2(hole.min.x, hole.max.y),
+            ),
+            Rect::from_min_max(
+                egui::pos2(hole.max.x, hole.min.y),
+                egui::pos2(screen.max.x, hole.max.y),
+            ),
+        ],
+        None => vec![screen],
+    };
+    // An area for each rectangle, as an area takes the clicks on all of itself. They are
+    // above the windows and below the tour, which is moved to the top after them.
+    // The hole is outlined on the first of them.
+    let mut outline = hole;
+    for (i, rect) in dimmed.into_iter().enumerate() {
+        if rect.width() <= 0.0 || rect.height() <= 0.0 {
+            continue;
+        }
+        Area::new(Id::new("tour_spotlight").with(i))
+            .order(Order::Foreground)
+            .fixed_pos(rect.min)
+            .constrain(false)
+            .show(ctx, |ui| {
+                ui.allocate_exact_size(rect.size(), Sense::click());
+                ui.painter().rect_filled(rect, 0.0, dim);
+                if let Some(hole) = outline.take() {
+                    let stroke = Stroke::new(2.0, ctx.style().visuals.selection.stroke.color);
+                    ctx.layer_painter(ui.layer_id())
+                        .rect_stroke(hole, 4.0, stroke);
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_have_messages_and_known_targets() {
+        let steps = load_steps();
+        assert!(!steps.is_empty());
+        let english = Translator::default();
+        let mut harness = crate::harness::Harness::new();
+        harness.settle();
+        let regions = harness.app.regions();
+        for step in &steps {
+            assert_ne!(english.get(&step.title), step.title, "missing message");
+            assert_ne!(english.get(&step.text), step.text, "missing message");
+            if let Some(target) = &step.target {
+                assert!(
+                    regions.iter().any(|(name, _)| name == target),
+                    "unknown target {target}"
+                );
+            }
+        }
+    }
+}
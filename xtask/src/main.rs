@@ -0,0 +1,257 @@
+//! Project automation. Run with `cargo xtask <command>`:
+//!
+//! * `generate` rewrites the web files that are derived from the crate metadata and theme:
+//!   the cache list in `assets/sw.js`, the names and colors in `assets/manifest.json`,
+//!   and the title and theme color in `index.html`.
+//! * `check` fails if any of those files are stale, e.g. after renaming the crate.
+//! * `stamp-sw [dir]` replaces `{{BUILD_HASH}}` in `<dir>/sw.js` with a hash of the wasm
+//!   binary in `<dir>`. Run by the post_build hook in `Trunk.toml`; `dir` defaults to trunk's
+//!   staging directory, `$TRUNK_STAGING_DIR`.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use serde_json::Value;
+
+const SW_BEGIN: &str = "// Generated by `cargo xtask generate`, do not edit by hand:\n";
+const SW_END: &str = "// End of generated code.\n";
+const BUILD_HASH: &str = "{{BUILD_HASH}}";
+
+fn main() -> ExitCode {
+    let command = std::env::args().nth(1);
+    let result = match command.as_deref() {
+        Some("generate") => generate(false),
+        Some("check") => generate(true),
+        Some("stamp-sw") => {
+            match std::env::args_os()
+                .nth(2)
+                .or_else(|| std::env::var_os("TRUNK_STAGING_DIR"))
+            {
+                Some(dir) => stamp_service_worker(Path::new(&dir)),
+                None => Err("usage: cargo xtask stamp-sw <dir>".to_owned()),
+            }
+        }
+        _ => Err("usage: cargo xtask <generate|check|stamp-sw [dir]>".to_owned()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// What the generated files are derived from.
+struct Metadata {
+    /// `package.name`, which is also the name of the js and wasm files trunk outputs.
+    crate_name: String,
+    /// Human readable name, e.g. "eframe template".
+    title: String,
+    /// Used in ids, e.g. "eframe-template".
+    short_name: String,
+    theme_color: String,
+}
+
+impl Metadata {
+    fn load(root: &Path) -> Result<Self, String> {
+        let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()))
+            .args([
+                "metadata",
+                "--no-deps",
+                "--format-version",
+                "1",
+                "--manifest-path",
+            ])
+            .arg(root.join("Cargo.toml"))
+            .output()
+            .map_err(|err| format!("failed to run cargo metadata: {err}"))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+        }
+        let metadata: Value =
+            serde_json::from_slice(&output.stdout).map_err(|err| err.to_string())?;
+        let crate_name = metadata["packages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|package| {
+                package["manifest_path"].as_str().map(Path::new) == Some(&root.join("Cargo.toml"))
+            })
+            .and_then(|package| package["name"].as_str())
+            .ok_or("couldn't find the app package in cargo metadata")?
+            .to_owned();
+
+        let panel_fill = eframe_template::style::my_dark_visuals().panel_fill;
+        let [r, g, b, _] = panel_fill.to_array();
+
+        Ok(Self {
+            title: crate_name.replace(['_', '-'], " "),
+            short_name: crate_name.replace('_', "-"),
+            theme_color: format!("#{r:02x}{g:02x}{b:02x}"),
+            crate_name,
+        })
+    }
+}
+
+fn generate(check: bool) -> Result<(), String> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_owned();
+    let metadata = Metadata::load(&root)?;
+
+    let index_path = root.join("index.html");
+    let index = read(&index_path)?;
+    let sw_path = root.join("assets/sw.js");
+    let manifest_path = root.join("assets/manifest.json");
+
+    let files = [
+        (
+            sw_path.clone(),
+            service_worker(&read(&sw_path)?, &metadata, &index)?,
+        ),
+        (
+            manifest_path.clone(),
+            manifest(&read(&manifest_path)?, &metadata)?,
+        ),
+        (index_path, index_html(&index, &metadata)?),
+    ];
+
+    let mut stale = Vec::new();
+    for (path, contents) in files {
+        if read(&path)? == contents {
+            continue;
+        }
+        if check {
+            stale.push(
+                path.strip_prefix(&root)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string(),
+            );
+        } else {
+            std::fs::write(&path, contents)
+                .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+            println!("updated {}", path.display());
+        }
+    }
+
+    if stale.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "stale generated files: {}\nrun `cargo xtask generate` and commit the result",
+            stale.join(", ")
+        ))
+    }
+}
+
+/// Stamps the `sw.js` in `dir`, a trunk build, with a hash of the wasm binary next to it, so
+/// every build gets its own cache.
+fn stamp_service_worker(dir: &Path) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| format!("failed to read {}: {err}", dir.display()))?;
+    let wasm = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.to_string_lossy().ends_with("_bg.wasm"))
+        .ok_or_else(|| format!("no *_bg.wasm in {}", dir.display()))?;
+    let wasm =
+        std::fs::read(&wasm).map_err(|err| format!("failed to read {}: {err}", wasm.display()))?;
+
+    let sw_path = dir.join("sw.js");
+    let sw = read(&sw_path)?;
+    if !sw.contains(BUILD_HASH) {
+        return Err(format!("{} has no {BUILD_HASH}", sw_path.display()));
+    }
+    let sw = sw.replace(BUILD_HASH, &format!("{:016x}", fnv1a(&wasm)));
+    std::fs::write(&sw_path, sw)
+        .map_err(|err| format!("failed to write {}: {err}", sw_path.display()))
+}
+
+/// The 64-bit FNV-1a hash of `bytes`. Only has to differ between builds.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("failed to read {}: {err}", path.display()))
+}
+
+/// Replaces the generated block at the top of `sw.js`.
+fn service_worker(sw: &str, metadata: &Metadata, index: &str) -> Result<String, String> {
+    let begin = sw
+        .find(SW_BEGIN)
+        .ok_or("assets/sw.js is missing the generated code marker")?
+        + SW_BEGIN.len();
+    let end = sw[begin..]
+        .find(SW_END)
+        .ok_or("assets/sw.js is missing the end of generated code marker")?
+        + begin;
+
+    let mut files = vec![
+        "./".to_owned(),
+        "./index.html".to_owned(),
+        format!("./{}.js", metadata.crate_name),
+        format!("./{}_bg.wasm", metadata.crate_name),
+    ];
+    // Everything trunk copies next to the app, except the worker itself.
+    files.extend(
+        copied_files(index)
+            .filter(|file| *file != "sw.js")
+            .map(|file| format!("./{file}")),
+    );
+
+    let mut block = format!(
+        "var cachePrefix = '{}-';\nvar filesToCache = [\n",
+        metadata.short_name
+    );
+    for file in files {
+        block += &format!("  '{file}',\n");
+    }
+    block += "];\n";
+
+    Ok(format!("{}{block}{}", &sw[..begin], &sw[end..]))
+}
+
+/// File names of `<link data-trunk rel="copy-file" href="...">` tags.
+fn copied_files(index: &str) -> impl Iterator<Item = &str> {
+    index
+        .lines()
+        .filter(|line| line.contains("data-trunk") && line.contains("rel=\"copy-file\""))
+        .filter_map(|line| {
+            let href = line.split("href=\"").nth(1)?.split('"').next()?;
+            href.rsplit('/').next()
+        })
+}
+
+fn manifest(manifest: &str, metadata: &Metadata) -> Result<String, String> {
+    let mut manifest: Value =
+        serde_json::from_str(manifest).map_err(|err| format!("assets/manifest.json: {err}"))?;
+    manifest["name"] = metadata.title.clone().into();
+    manifest["short_name"] = metadata.short_name.clone().into();
+    manifest["background_color"] = metadata.theme_color.clone().into();
+    manifest["theme_color"] = metadata.theme_color.clone().into();
+    Ok(serde_json::to_string_pretty(&manifest).unwrap() + "\n")
+}
+
+fn index_html(index: &str, metadata: &Metadata) -> Result<String, String> {
+    let index = replace_between(index, "<title>", "</title>", &metadata.title)
+        .ok_or("index.html has no <title>")?;
+    replace_between(
+        &index,
+        r#"<meta name="theme-color" media="(prefers-color-scheme: dark)" content=""#,
+        r#"">"#,
+        &metadata.theme_color,
+    )
+    .ok_or_else(|| "index.html has no dark theme-color meta tag".to_owned())
+}
+
+fn replace_between(text: &str, start: &str, end: &str, replacement: &str) -> Option<String> {
+    let begin = text.find(start)? + start.len();
+    let end = text[begin..].find(end)? + begin;
+    Some(format!("{}{replacement}{}", &text[..begin], &text[end..]))
+}